@@ -25,6 +25,36 @@ app.get("/stock/chart/:symbol", async (req, res) => {
   }
 });
 
+app.get("/stock/events/:symbol", async (req, res) => {
+  try {
+    const { period1, period2 } = req.query;
+    const result = await yahooFinance.chart(req.params.symbol, {
+      interval: "1d",
+      period1: period1,
+      period2: period2,
+      events: "div|split"
+    });
+    const events = result.events || {};
+
+    res.json({
+      meta: result.meta,
+      events: {
+        dividends: (events.dividends || []).map((dividend) => ({
+          date: dividend.date,
+          amount: dividend.amount
+        })),
+        splits: (events.splits || []).map((split) => ({
+          date: split.date,
+          numerator: split.numerator,
+          denominator: split.denominator
+        }))
+      }
+    });
+  } catch (error) {
+    res.status(500).json({ error: error.toString() });
+  }
+});
+
 app.get("/search-stocks/:symbol", async (req, res) => {
   const query = req.params.symbol;
 
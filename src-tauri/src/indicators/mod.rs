@@ -0,0 +1,266 @@
+pub mod momentum;
+pub mod trend;
+pub mod volatility;
+pub mod volume;
+
+use once_cell::sync::Lazy;
+use serde::Serialize;
+
+use crate::strategies::{resolve_param_specs, ParamSpec, StrategyParams};
+use crate::types::{CustomQuote, IndicatorData, Timeframe};
+use crate::utils::indicator_utils::shift_series;
+use momentum::{Cci, Mfi, Roc, Rsi, Stochastic, UltimateOscillator, WilliamsR};
+use trend::{Adx, Aroon, Ema, Ichimoku, Macd, ParabolicSar, Sma, Supertrend, Vortex};
+use volatility::{Atr, BollingerBands, BollingerBandwidth, BollingerPercentB, Donchian, Keltner};
+use volume::{
+    AccumulationDistribution, AnchoredVwap, ChaikinMoneyFlow, ChaikinOscillator, Obv, SessionVwap, Volume,
+};
+
+pub type IndicatorParams = StrategyParams;
+
+/// Where the chart draws an indicator: on top of the candles, in the volume strip at the
+/// bottom of the price pane, or in its own pane below the price chart.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Pane {
+    Overlay,
+    Volume,
+    Separate,
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SeriesStyle {
+    Line,
+    Histogram,
+    Points,
+}
+
+/// One output series and how the chart should style it.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct OutputSpec {
+    pub name: &'static str,
+    pub style: SeriesStyle,
+    pub color: &'static str,
+}
+
+impl OutputSpec {
+    pub fn line(name: &'static str, color: &'static str) -> Self {
+        Self { name, style: SeriesStyle::Line, color }
+    }
+
+    pub fn histogram(name: &'static str, color: &'static str) -> Self {
+        Self { name, style: SeriesStyle::Histogram, color }
+    }
+
+    pub fn points(name: &'static str, color: &'static str) -> Self {
+        Self { name, style: SeriesStyle::Points, color }
+    }
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct IndicatorInfo {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub pane: Pane,
+    pub params: Vec<ParamSpec>,
+    pub outputs: Vec<OutputSpec>,
+    pub presets: Vec<Vec<f64>>,
+}
+
+/// A chart indicator. `calculate` returns one series per entry of `outputs`, in order.
+pub trait Indicator: Send + Sync {
+    fn id(&self) -> &'static str;
+
+    fn name(&self) -> &'static str;
+
+    fn pane(&self) -> Pane;
+
+    fn params(&self) -> Vec<ParamSpec>;
+
+    fn outputs(&self) -> Vec<OutputSpec>;
+
+    fn calculate(&self, data: &[CustomQuote], params: &IndicatorParams) -> Vec<Vec<IndicatorData>>;
+
+    /// Bars each output is displaced by when charted: positive values project into future bar
+    /// times, negative values shift back. Defaults to no displacement.
+    fn offsets(&self, _params: &IndicatorParams) -> Vec<i64> {
+        vec![0; self.outputs().len()]
+    }
+
+    /// Parameter sets offered in the indicator menu; defaults to the default values.
+    fn presets(&self) -> Vec<Vec<f64>> {
+        vec![self.params().iter().map(|spec| spec.default).collect()]
+    }
+
+    fn info(&self) -> IndicatorInfo {
+        IndicatorInfo {
+            id: self.id(),
+            name: self.name(),
+            pane: self.pane(),
+            params: self.params(),
+            outputs: self.outputs(),
+            presets: self.presets(),
+        }
+    }
+
+    fn resolve_params(&self, params: &IndicatorParams) -> Result<IndicatorParams, String> {
+        resolve_param_specs(self.id(), &self.params(), params)
+    }
+
+    /// Maps positional values onto the parameters in declaration order.
+    fn params_from_values(&self, values: &[f64]) -> Result<IndicatorParams, String> {
+        let specs = self.params();
+        if values.len() > specs.len() {
+            return Err(format!(
+                "{} takes at most {} parameters",
+                self.id(),
+                specs.len()
+            ));
+        }
+        Ok(specs
+            .iter()
+            .zip(values)
+            .map(|(spec, value)| (spec.name.to_string(), *value))
+            .collect())
+    }
+}
+
+pub static INDICATORS: Lazy<Vec<Box<dyn Indicator>>> = Lazy::new(|| {
+    vec![
+        Box::new(Sma),
+        Box::new(Ema),
+        Box::new(Rsi),
+        Box::new(Stochastic),
+        Box::new(WilliamsR),
+        Box::new(Cci),
+        Box::new(Roc),
+        Box::new(Mfi),
+        Box::new(UltimateOscillator),
+        Box::new(Macd),
+        Box::new(Adx),
+        Box::new(ParabolicSar),
+        Box::new(Supertrend),
+        Box::new(Aroon),
+        Box::new(Vortex),
+        Box::new(Ichimoku),
+        Box::new(Volume),
+        Box::new(BollingerBands),
+        Box::new(BollingerPercentB),
+        Box::new(BollingerBandwidth),
+        Box::new(Atr),
+        Box::new(Keltner),
+        Box::new(Donchian),
+        Box::new(Obv),
+        Box::new(AccumulationDistribution),
+        Box::new(ChaikinMoneyFlow),
+        Box::new(ChaikinOscillator),
+        Box::new(SessionVwap),
+        Box::new(AnchoredVwap),
+    ]
+});
+
+pub fn find_indicator(id: &str) -> Result<&'static dyn Indicator, String> {
+    INDICATORS
+        .iter()
+        .find(|indicator| indicator.id() == id)
+        .map(|indicator| indicator.as_ref())
+        .ok_or_else(|| format!("Unknown indicator: {}", id))
+}
+
+/// Resolves the parameters, calculates every output series and applies its displacement.
+pub fn compute_indicator(
+    indicator: &dyn Indicator,
+    data: &[CustomQuote],
+    timeframe: Timeframe,
+    params: &IndicatorParams,
+) -> Result<Vec<Vec<IndicatorData>>, String> {
+    let params = indicator.resolve_params(params)?;
+    if data.is_empty() {
+        return Ok(vec![Vec::new(); indicator.outputs().len()]);
+    }
+    let offsets = indicator.offsets(&params);
+    Ok(indicator
+        .calculate(data, &params)
+        .into_iter()
+        .zip(offsets)
+        .map(|(series, offset)| match offset {
+            0 => series,
+            _ => shift_series(&series, data, offset, timeframe),
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategies::param_usize;
+    use chrono::{Duration, TimeZone, Utc};
+
+    fn quotes(closes: &[f64]) -> Vec<CustomQuote> {
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        closes
+            .iter()
+            .enumerate()
+            .map(|(i, close)| CustomQuote {
+                time: start + Duration::days(i as i64),
+                open: Some(*close),
+                high: Some(close + 1.0),
+                low: Some(close - 1.0),
+                close: Some(*close),
+                volume: Some(1000),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_registry_outputs_match_calculations() {
+        let data = quotes(&[10.0, 11.0, 12.0, 11.0, 13.0, 14.0, 13.0, 15.0, 16.0, 15.0]);
+        let mut ids: Vec<&str> = INDICATORS.iter().map(|indicator| indicator.id()).collect();
+        ids.sort();
+        ids.dedup();
+        assert_eq!(ids.len(), INDICATORS.len());
+
+        for indicator in INDICATORS.iter() {
+            let mut params = IndicatorParams::new();
+            for spec in indicator.params() {
+                params.insert(spec.name.to_string(), spec.min.max(2.0).min(spec.max));
+            }
+            let series = compute_indicator(indicator.as_ref(), &data, Timeframe::OneDay, &params).unwrap();
+            assert_eq!(series.len(), indicator.outputs().len(), "{}", indicator.id());
+            assert_eq!(indicator.offsets(&params).len(), series.len(), "{}", indicator.id());
+            for preset in indicator.presets() {
+                let params = indicator.params_from_values(&preset).unwrap();
+                assert!(indicator.resolve_params(&params).is_ok(), "{}", indicator.id());
+            }
+        }
+    }
+
+    #[test]
+    fn test_params_from_values() {
+        let macd = find_indicator("MACD").unwrap();
+        let params = macd.resolve_params(&macd.params_from_values(&[5.0, 10.0]).unwrap()).unwrap();
+        assert_eq!(param_usize(&params, "short"), 5);
+        assert_eq!(param_usize(&params, "long"), 10);
+        assert_eq!(param_usize(&params, "signal"), 9);
+        assert!(macd.params_from_values(&[1.0, 2.0, 3.0, 4.0]).is_err());
+        let sma = find_indicator("SMA").unwrap();
+        assert!(sma.resolve_params(&sma.params_from_values(&[0.0]).unwrap()).is_err());
+        assert!(find_indicator("KAMA").is_err());
+    }
+
+    #[test]
+    fn test_ichimoku_projection() {
+        let data = quotes(&[10.0, 11.0, 12.0, 11.0, 13.0]);
+        let ichimoku = find_indicator("ICHIMOKU").unwrap();
+        let params = ichimoku.params_from_values(&[1.0, 2.0, 3.0, 2.0]).unwrap();
+        let series = compute_indicator(ichimoku, &data, Timeframe::OneDay, &params).unwrap();
+        let last = data.last().unwrap().time;
+
+        assert_eq!(series[0].last().unwrap().time, last);
+        // 2024-01-05 is a Friday: two daily bars later is the following Tuesday.
+        assert_eq!(series[2].last().unwrap().time, last + Duration::days(4));
+        assert_eq!(series[4].len(), data.len() - 2);
+        assert_eq!(series[4][0].time, data[0].time);
+    }
+}
@@ -0,0 +1,239 @@
+use crate::strategies::{param_usize, ParamSpec};
+use crate::types::{CustomQuote, IndicatorData};
+use crate::utils::indicator_utils::{
+    calculate_cci, calculate_mfi, calculate_roc, calculate_rsi, calculate_stochastic,
+    calculate_ultimate_oscillator, calculate_williams_r,
+};
+
+use super::{Indicator, IndicatorParams, OutputSpec, Pane};
+
+pub struct Rsi;
+
+impl Indicator for Rsi {
+    fn id(&self) -> &'static str {
+        "RSI"
+    }
+
+    fn name(&self) -> &'static str {
+        "Relative Strength Index"
+    }
+
+    fn pane(&self) -> Pane {
+        Pane::Separate
+    }
+
+    fn params(&self) -> Vec<ParamSpec> {
+        vec![ParamSpec::integer("period", "Period", 14, 1, 500)]
+    }
+
+    fn outputs(&self) -> Vec<OutputSpec> {
+        vec![OutputSpec::line("rsi", "#64ff64")]
+    }
+
+    fn presets(&self) -> Vec<Vec<f64>> {
+        vec![vec![7.0], vec![14.0], vec![21.0]]
+    }
+
+    fn calculate(&self, data: &[CustomQuote], params: &IndicatorParams) -> Vec<Vec<IndicatorData>> {
+        vec![calculate_rsi(data, param_usize(params, "period"))]
+    }
+}
+
+/// Full stochastic; the presets cover the fast (%K unsmoothed) and slow (3-bar %K) variants.
+pub struct Stochastic;
+
+impl Indicator for Stochastic {
+    fn id(&self) -> &'static str {
+        "STOCH"
+    }
+
+    fn name(&self) -> &'static str {
+        "Stochastic Oscillator"
+    }
+
+    fn pane(&self) -> Pane {
+        Pane::Separate
+    }
+
+    fn params(&self) -> Vec<ParamSpec> {
+        vec![
+            ParamSpec::integer("k_period", "%K period", 14, 1, 500),
+            ParamSpec::integer("k_smoothing", "%K smoothing", 3, 1, 100),
+            ParamSpec::integer("d_period", "%D period", 3, 1, 100),
+        ]
+    }
+
+    fn outputs(&self) -> Vec<OutputSpec> {
+        vec![
+            OutputSpec::line("k", "#2962ff"),
+            OutputSpec::line("d", "#ff6d00"),
+        ]
+    }
+
+    fn presets(&self) -> Vec<Vec<f64>> {
+        vec![vec![14.0, 1.0, 3.0], vec![14.0, 3.0, 3.0], vec![14.0, 3.0, 5.0]]
+    }
+
+    fn calculate(&self, data: &[CustomQuote], params: &IndicatorParams) -> Vec<Vec<IndicatorData>> {
+        let (k, d) = calculate_stochastic(
+            data,
+            param_usize(params, "k_period"),
+            param_usize(params, "k_smoothing"),
+            param_usize(params, "d_period"),
+        );
+        vec![k, d]
+    }
+}
+
+pub struct WilliamsR;
+
+impl Indicator for WilliamsR {
+    fn id(&self) -> &'static str {
+        "WILLR"
+    }
+
+    fn name(&self) -> &'static str {
+        "Williams %R"
+    }
+
+    fn pane(&self) -> Pane {
+        Pane::Separate
+    }
+
+    fn params(&self) -> Vec<ParamSpec> {
+        vec![ParamSpec::integer("period", "Period", 14, 1, 500)]
+    }
+
+    fn outputs(&self) -> Vec<OutputSpec> {
+        vec![OutputSpec::line("williams_r", "#7e57c2")]
+    }
+
+    fn calculate(&self, data: &[CustomQuote], params: &IndicatorParams) -> Vec<Vec<IndicatorData>> {
+        vec![calculate_williams_r(data, param_usize(params, "period"))]
+    }
+}
+
+pub struct Cci;
+
+impl Indicator for Cci {
+    fn id(&self) -> &'static str {
+        "CCI"
+    }
+
+    fn name(&self) -> &'static str {
+        "Commodity Channel Index"
+    }
+
+    fn pane(&self) -> Pane {
+        Pane::Separate
+    }
+
+    fn params(&self) -> Vec<ParamSpec> {
+        vec![ParamSpec::integer("period", "Period", 20, 1, 500)]
+    }
+
+    fn outputs(&self) -> Vec<OutputSpec> {
+        vec![OutputSpec::line("cci", "#00897b")]
+    }
+
+    fn calculate(&self, data: &[CustomQuote], params: &IndicatorParams) -> Vec<Vec<IndicatorData>> {
+        vec![calculate_cci(data, param_usize(params, "period"))]
+    }
+}
+
+pub struct Roc;
+
+impl Indicator for Roc {
+    fn id(&self) -> &'static str {
+        "ROC"
+    }
+
+    fn name(&self) -> &'static str {
+        "Rate of Change"
+    }
+
+    fn pane(&self) -> Pane {
+        Pane::Separate
+    }
+
+    fn params(&self) -> Vec<ParamSpec> {
+        vec![ParamSpec::integer("period", "Period", 12, 1, 500)]
+    }
+
+    fn outputs(&self) -> Vec<OutputSpec> {
+        vec![OutputSpec::line("roc", "#f06292")]
+    }
+
+    fn presets(&self) -> Vec<Vec<f64>> {
+        vec![vec![9.0], vec![12.0], vec![25.0]]
+    }
+
+    fn calculate(&self, data: &[CustomQuote], params: &IndicatorParams) -> Vec<Vec<IndicatorData>> {
+        vec![calculate_roc(data, param_usize(params, "period"))]
+    }
+}
+
+pub struct Mfi;
+
+impl Indicator for Mfi {
+    fn id(&self) -> &'static str {
+        "MFI"
+    }
+
+    fn name(&self) -> &'static str {
+        "Money Flow Index"
+    }
+
+    fn pane(&self) -> Pane {
+        Pane::Separate
+    }
+
+    fn params(&self) -> Vec<ParamSpec> {
+        vec![ParamSpec::integer("period", "Period", 14, 1, 500)]
+    }
+
+    fn outputs(&self) -> Vec<OutputSpec> {
+        vec![OutputSpec::line("mfi", "#ffb300")]
+    }
+
+    fn calculate(&self, data: &[CustomQuote], params: &IndicatorParams) -> Vec<Vec<IndicatorData>> {
+        vec![calculate_mfi(data, param_usize(params, "period"))]
+    }
+}
+
+pub struct UltimateOscillator;
+
+impl Indicator for UltimateOscillator {
+    fn id(&self) -> &'static str {
+        "UO"
+    }
+
+    fn name(&self) -> &'static str {
+        "Ultimate Oscillator"
+    }
+
+    fn pane(&self) -> Pane {
+        Pane::Separate
+    }
+
+    fn params(&self) -> Vec<ParamSpec> {
+        vec![
+            ParamSpec::integer("short", "Short period", 7, 1, 500),
+            ParamSpec::integer("medium", "Medium period", 14, 1, 500),
+            ParamSpec::integer("long", "Long period", 28, 1, 500),
+        ]
+    }
+
+    fn outputs(&self) -> Vec<OutputSpec> {
+        vec![OutputSpec::line("uo", "#ef5350")]
+    }
+
+    fn calculate(&self, data: &[CustomQuote], params: &IndicatorParams) -> Vec<Vec<IndicatorData>> {
+        vec![calculate_ultimate_oscillator(
+            data,
+            param_usize(params, "short"),
+            param_usize(params, "medium"),
+            param_usize(params, "long"),
+        )]
+    }
+}
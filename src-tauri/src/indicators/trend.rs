@@ -0,0 +1,346 @@
+use crate::strategies::{param_f64, param_usize, ParamSpec};
+use crate::types::{CustomQuote, IndicatorData};
+use crate::utils::indicator_utils::{
+    calculate_aroon, calculate_dmi, calculate_ema, calculate_ichimoku, calculate_macd, calculate_parabolic_sar,
+    calculate_sma, calculate_supertrend, calculate_vortex,
+};
+
+use super::{Indicator, IndicatorParams, OutputSpec, Pane};
+
+const MA_PRESETS: [usize; 7] = [5, 8, 13, 21, 50, 100, 200];
+
+fn ma_presets() -> Vec<Vec<f64>> {
+    MA_PRESETS.iter().map(|period| vec![*period as f64]).collect()
+}
+
+pub struct Sma;
+
+impl Indicator for Sma {
+    fn id(&self) -> &'static str {
+        "SMA"
+    }
+
+    fn name(&self) -> &'static str {
+        "Simple Moving Average"
+    }
+
+    fn pane(&self) -> Pane {
+        Pane::Overlay
+    }
+
+    fn params(&self) -> Vec<ParamSpec> {
+        vec![ParamSpec::integer("period", "Period", 20, 1, 1000)]
+    }
+
+    fn outputs(&self) -> Vec<OutputSpec> {
+        vec![OutputSpec::line("sma", "#ff6464")]
+    }
+
+    fn presets(&self) -> Vec<Vec<f64>> {
+        ma_presets()
+    }
+
+    fn calculate(&self, data: &[CustomQuote], params: &IndicatorParams) -> Vec<Vec<IndicatorData>> {
+        vec![calculate_sma(data, param_usize(params, "period"))]
+    }
+}
+
+pub struct Ema;
+
+impl Indicator for Ema {
+    fn id(&self) -> &'static str {
+        "EMA"
+    }
+
+    fn name(&self) -> &'static str {
+        "Exponential Moving Average"
+    }
+
+    fn pane(&self) -> Pane {
+        Pane::Overlay
+    }
+
+    fn params(&self) -> Vec<ParamSpec> {
+        vec![ParamSpec::integer("period", "Period", 20, 1, 1000)]
+    }
+
+    fn outputs(&self) -> Vec<OutputSpec> {
+        vec![OutputSpec::line("ema", "#6464ff")]
+    }
+
+    fn presets(&self) -> Vec<Vec<f64>> {
+        ma_presets()
+    }
+
+    fn calculate(&self, data: &[CustomQuote], params: &IndicatorParams) -> Vec<Vec<IndicatorData>> {
+        vec![calculate_ema(data, param_usize(params, "period"))]
+    }
+}
+
+pub struct Macd;
+
+impl Indicator for Macd {
+    fn id(&self) -> &'static str {
+        "MACD"
+    }
+
+    fn name(&self) -> &'static str {
+        "Moving Average Convergence Divergence"
+    }
+
+    fn pane(&self) -> Pane {
+        Pane::Separate
+    }
+
+    fn params(&self) -> Vec<ParamSpec> {
+        vec![
+            ParamSpec::integer("short", "Short EMA", 12, 1, 500),
+            ParamSpec::integer("long", "Long EMA", 26, 1, 500),
+            ParamSpec::integer("signal", "Signal EMA", 9, 1, 500),
+        ]
+    }
+
+    fn outputs(&self) -> Vec<OutputSpec> {
+        vec![
+            OutputSpec::line("macd", "#855085"),
+            OutputSpec::line("signal", "#b9b964"),
+            OutputSpec::histogram("histogram", "#64ffff"),
+        ]
+    }
+
+    fn calculate(&self, data: &[CustomQuote], params: &IndicatorParams) -> Vec<Vec<IndicatorData>> {
+        let (macd_line, signal_line, histogram) = calculate_macd(
+            data,
+            param_usize(params, "short"),
+            param_usize(params, "long"),
+            param_usize(params, "signal"),
+        );
+        vec![macd_line, signal_line, histogram]
+    }
+}
+
+pub struct Adx;
+
+impl Indicator for Adx {
+    fn id(&self) -> &'static str {
+        "ADX"
+    }
+
+    fn name(&self) -> &'static str {
+        "Average Directional Index"
+    }
+
+    fn pane(&self) -> Pane {
+        Pane::Separate
+    }
+
+    fn params(&self) -> Vec<ParamSpec> {
+        vec![ParamSpec::integer("period", "Period", 14, 1, 500)]
+    }
+
+    fn outputs(&self) -> Vec<OutputSpec> {
+        vec![
+            OutputSpec::line("adx", "#ffb300"),
+            OutputSpec::line("plus_di", "#26a69a"),
+            OutputSpec::line("minus_di", "#ef5350"),
+        ]
+    }
+
+    fn calculate(&self, data: &[CustomQuote], params: &IndicatorParams) -> Vec<Vec<IndicatorData>> {
+        let dmi = calculate_dmi(data, param_usize(params, "period"));
+        vec![dmi.adx, dmi.plus_di, dmi.minus_di]
+    }
+}
+
+pub struct ParabolicSar;
+
+impl Indicator for ParabolicSar {
+    fn id(&self) -> &'static str {
+        "PSAR"
+    }
+
+    fn name(&self) -> &'static str {
+        "Parabolic SAR"
+    }
+
+    fn pane(&self) -> Pane {
+        Pane::Overlay
+    }
+
+    fn params(&self) -> Vec<ParamSpec> {
+        vec![
+            ParamSpec::float("step", "Acceleration step", 0.02, 0.001, 1.0),
+            ParamSpec::float("max_step", "Max acceleration", 0.2, 0.001, 1.0),
+        ]
+    }
+
+    fn outputs(&self) -> Vec<OutputSpec> {
+        vec![OutputSpec::points("sar", "#ab47bc")]
+    }
+
+    fn calculate(&self, data: &[CustomQuote], params: &IndicatorParams) -> Vec<Vec<IndicatorData>> {
+        vec![calculate_parabolic_sar(
+            data,
+            param_f64(params, "step"),
+            param_f64(params, "max_step"),
+        )]
+    }
+}
+
+pub struct Supertrend;
+
+impl Indicator for Supertrend {
+    fn id(&self) -> &'static str {
+        "SUPERTREND"
+    }
+
+    fn name(&self) -> &'static str {
+        "Supertrend"
+    }
+
+    fn pane(&self) -> Pane {
+        Pane::Overlay
+    }
+
+    fn params(&self) -> Vec<ParamSpec> {
+        vec![
+            ParamSpec::integer("period", "ATR period", 10, 1, 500),
+            ParamSpec::float("multiplier", "ATR multiple", 3.0, 0.1, 10.0),
+        ]
+    }
+
+    fn outputs(&self) -> Vec<OutputSpec> {
+        vec![OutputSpec::line("supertrend", "#00897b")]
+    }
+
+    fn calculate(&self, data: &[CustomQuote], params: &IndicatorParams) -> Vec<Vec<IndicatorData>> {
+        vec![calculate_supertrend(
+            data,
+            param_usize(params, "period"),
+            param_f64(params, "multiplier"),
+        )]
+    }
+}
+
+pub struct Aroon;
+
+impl Indicator for Aroon {
+    fn id(&self) -> &'static str {
+        "AROON"
+    }
+
+    fn name(&self) -> &'static str {
+        "Aroon"
+    }
+
+    fn pane(&self) -> Pane {
+        Pane::Separate
+    }
+
+    fn params(&self) -> Vec<ParamSpec> {
+        vec![ParamSpec::integer("period", "Period", 25, 1, 500)]
+    }
+
+    fn outputs(&self) -> Vec<OutputSpec> {
+        vec![
+            OutputSpec::line("up", "#26a69a"),
+            OutputSpec::line("down", "#ef5350"),
+            OutputSpec::histogram("oscillator", "#90a4ae"),
+        ]
+    }
+
+    fn calculate(&self, data: &[CustomQuote], params: &IndicatorParams) -> Vec<Vec<IndicatorData>> {
+        let (up, down, oscillator) = calculate_aroon(data, param_usize(params, "period"));
+        vec![up, down, oscillator]
+    }
+}
+
+pub struct Vortex;
+
+impl Indicator for Vortex {
+    fn id(&self) -> &'static str {
+        "VORTEX"
+    }
+
+    fn name(&self) -> &'static str {
+        "Vortex Indicator"
+    }
+
+    fn pane(&self) -> Pane {
+        Pane::Separate
+    }
+
+    fn params(&self) -> Vec<ParamSpec> {
+        vec![ParamSpec::integer("period", "Period", 14, 1, 500)]
+    }
+
+    fn outputs(&self) -> Vec<OutputSpec> {
+        vec![
+            OutputSpec::line("plus", "#26a69a"),
+            OutputSpec::line("minus", "#ef5350"),
+        ]
+    }
+
+    fn calculate(&self, data: &[CustomQuote], params: &IndicatorParams) -> Vec<Vec<IndicatorData>> {
+        let (plus, minus) = calculate_vortex(data, param_usize(params, "period"));
+        vec![plus, minus]
+    }
+}
+
+/// Ichimoku Kinko Hyo. The Senkou spans are projected `displacement` bars into the future and
+/// the Chikou span is plotted `displacement` bars back.
+pub struct Ichimoku;
+
+impl Indicator for Ichimoku {
+    fn id(&self) -> &'static str {
+        "ICHIMOKU"
+    }
+
+    fn name(&self) -> &'static str {
+        "Ichimoku Kinko Hyo"
+    }
+
+    fn pane(&self) -> Pane {
+        Pane::Overlay
+    }
+
+    fn params(&self) -> Vec<ParamSpec> {
+        vec![
+            ParamSpec::integer("tenkan", "Tenkan period", 9, 1, 500),
+            ParamSpec::integer("kijun", "Kijun period", 26, 1, 500),
+            ParamSpec::integer("senkou_b", "Senkou B period", 52, 1, 500),
+            ParamSpec::integer("displacement", "Displacement", 26, 1, 500),
+        ]
+    }
+
+    fn outputs(&self) -> Vec<OutputSpec> {
+        vec![
+            OutputSpec::line("tenkan", "#2962ff"),
+            OutputSpec::line("kijun", "#b71c1c"),
+            OutputSpec::line("senkou_a", "#43a047"),
+            OutputSpec::line("senkou_b", "#e53935"),
+            OutputSpec::line("chikou", "#9e9e9e"),
+        ]
+    }
+
+    fn offsets(&self, params: &IndicatorParams) -> Vec<i64> {
+        let displacement = param_usize(params, "displacement") as i64;
+        vec![0, 0, displacement, displacement, -displacement]
+    }
+
+    fn calculate(&self, data: &[CustomQuote], params: &IndicatorParams) -> Vec<Vec<IndicatorData>> {
+        let ichimoku = calculate_ichimoku(
+            data,
+            param_usize(params, "tenkan"),
+            param_usize(params, "kijun"),
+            param_usize(params, "senkou_b"),
+        );
+        vec![
+            ichimoku.tenkan,
+            ichimoku.kijun,
+            ichimoku.senkou_a,
+            ichimoku.senkou_b,
+            ichimoku.chikou,
+        ]
+    }
+}
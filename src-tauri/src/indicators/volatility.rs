@@ -0,0 +1,208 @@
+use crate::strategies::{param_f64, param_usize, ParamSpec};
+use crate::types::{CustomQuote, IndicatorData};
+use crate::utils::indicator_utils::{
+    calculate_atr, calculate_bandwidth, calculate_bollinger_bands, calculate_donchian_channels,
+    calculate_keltner_channels, calculate_percent_b, Channel,
+};
+
+use super::{Indicator, IndicatorParams, OutputSpec, Pane};
+
+fn bollinger_params() -> Vec<ParamSpec> {
+    vec![
+        ParamSpec::integer("period", "Period", 20, 1, 500),
+        ParamSpec::float("multiplier", "Std. deviations", 2.0, 0.1, 10.0),
+    ]
+}
+
+fn bollinger(data: &[CustomQuote], params: &IndicatorParams) -> Channel {
+    calculate_bollinger_bands(data, param_usize(params, "period"), param_f64(params, "multiplier"))
+}
+
+fn channel_outputs(color: &'static str, middle_color: &'static str) -> Vec<OutputSpec> {
+    vec![
+        OutputSpec::line("upper", color),
+        OutputSpec::line("middle", middle_color),
+        OutputSpec::line("lower", color),
+    ]
+}
+
+fn channel_series(channel: Channel) -> Vec<Vec<IndicatorData>> {
+    vec![channel.upper, channel.middle, channel.lower]
+}
+
+pub struct BollingerBands;
+
+impl Indicator for BollingerBands {
+    fn id(&self) -> &'static str {
+        "BB"
+    }
+
+    fn name(&self) -> &'static str {
+        "Bollinger Bands"
+    }
+
+    fn pane(&self) -> Pane {
+        Pane::Overlay
+    }
+
+    fn params(&self) -> Vec<ParamSpec> {
+        bollinger_params()
+    }
+
+    fn outputs(&self) -> Vec<OutputSpec> {
+        channel_outputs("#2962ff", "#ff6d00")
+    }
+
+    fn calculate(&self, data: &[CustomQuote], params: &IndicatorParams) -> Vec<Vec<IndicatorData>> {
+        channel_series(bollinger(data, params))
+    }
+}
+
+pub struct BollingerPercentB;
+
+impl Indicator for BollingerPercentB {
+    fn id(&self) -> &'static str {
+        "BBP"
+    }
+
+    fn name(&self) -> &'static str {
+        "Bollinger %B"
+    }
+
+    fn pane(&self) -> Pane {
+        Pane::Separate
+    }
+
+    fn params(&self) -> Vec<ParamSpec> {
+        bollinger_params()
+    }
+
+    fn outputs(&self) -> Vec<OutputSpec> {
+        vec![OutputSpec::line("percent_b", "#2962ff")]
+    }
+
+    fn calculate(&self, data: &[CustomQuote], params: &IndicatorParams) -> Vec<Vec<IndicatorData>> {
+        vec![calculate_percent_b(data, &bollinger(data, params))]
+    }
+}
+
+pub struct BollingerBandwidth;
+
+impl Indicator for BollingerBandwidth {
+    fn id(&self) -> &'static str {
+        "BBW"
+    }
+
+    fn name(&self) -> &'static str {
+        "Bollinger Bandwidth"
+    }
+
+    fn pane(&self) -> Pane {
+        Pane::Separate
+    }
+
+    fn params(&self) -> Vec<ParamSpec> {
+        bollinger_params()
+    }
+
+    fn outputs(&self) -> Vec<OutputSpec> {
+        vec![OutputSpec::line("bandwidth", "#ff6d00")]
+    }
+
+    fn calculate(&self, data: &[CustomQuote], params: &IndicatorParams) -> Vec<Vec<IndicatorData>> {
+        vec![calculate_bandwidth(&bollinger(data, params))]
+    }
+}
+
+pub struct Atr;
+
+impl Indicator for Atr {
+    fn id(&self) -> &'static str {
+        "ATR"
+    }
+
+    fn name(&self) -> &'static str {
+        "Average True Range"
+    }
+
+    fn pane(&self) -> Pane {
+        Pane::Separate
+    }
+
+    fn params(&self) -> Vec<ParamSpec> {
+        vec![ParamSpec::integer("period", "Period", 14, 1, 500)]
+    }
+
+    fn outputs(&self) -> Vec<OutputSpec> {
+        vec![OutputSpec::line("atr", "#b71c1c")]
+    }
+
+    fn calculate(&self, data: &[CustomQuote], params: &IndicatorParams) -> Vec<Vec<IndicatorData>> {
+        vec![calculate_atr(data, param_usize(params, "period"))]
+    }
+}
+
+pub struct Keltner;
+
+impl Indicator for Keltner {
+    fn id(&self) -> &'static str {
+        "KC"
+    }
+
+    fn name(&self) -> &'static str {
+        "Keltner Channels"
+    }
+
+    fn pane(&self) -> Pane {
+        Pane::Overlay
+    }
+
+    fn params(&self) -> Vec<ParamSpec> {
+        vec![
+            ParamSpec::integer("period", "EMA period", 20, 1, 500),
+            ParamSpec::integer("atr_period", "ATR period", 10, 1, 500),
+            ParamSpec::float("multiplier", "ATR multiple", 2.0, 0.1, 10.0),
+        ]
+    }
+
+    fn outputs(&self) -> Vec<OutputSpec> {
+        channel_outputs("#00897b", "#4db6ac")
+    }
+
+    fn calculate(&self, data: &[CustomQuote], params: &IndicatorParams) -> Vec<Vec<IndicatorData>> {
+        channel_series(calculate_keltner_channels(
+            data,
+            param_usize(params, "period"),
+            param_usize(params, "atr_period"),
+            param_f64(params, "multiplier"),
+        ))
+    }
+}
+
+pub struct Donchian;
+
+impl Indicator for Donchian {
+    fn id(&self) -> &'static str {
+        "DC"
+    }
+
+    fn name(&self) -> &'static str {
+        "Donchian Channels"
+    }
+
+    fn pane(&self) -> Pane {
+        Pane::Overlay
+    }
+
+    fn params(&self) -> Vec<ParamSpec> {
+        vec![ParamSpec::integer("period", "Period", 20, 1, 500)]
+    }
+
+    fn outputs(&self) -> Vec<OutputSpec> {
+        channel_outputs("#7e57c2", "#b39ddb")
+    }
+
+    fn calculate(&self, data: &[CustomQuote], params: &IndicatorParams) -> Vec<Vec<IndicatorData>> {
+        channel_series(calculate_donchian_channels(data, param_usize(params, "period")))
+    }
+}
@@ -0,0 +1,260 @@
+use chrono::{TimeZone, Utc};
+
+use crate::strategies::{param_f64, param_usize, ParamSpec};
+use crate::types::{CustomQuote, IndicatorData};
+use crate::utils::indicator_utils::{
+    calculate_accumulation_distribution, calculate_chaikin_money_flow, calculate_chaikin_oscillator,
+    calculate_obv, calculate_volume, calculate_vwap, Vwap, VwapAnchor,
+};
+
+use super::{Indicator, IndicatorParams, OutputSpec, Pane};
+
+pub struct Volume;
+
+impl Indicator for Volume {
+    fn id(&self) -> &'static str {
+        "VOLUME"
+    }
+
+    fn name(&self) -> &'static str {
+        "Volume"
+    }
+
+    fn pane(&self) -> Pane {
+        Pane::Volume
+    }
+
+    fn params(&self) -> Vec<ParamSpec> {
+        Vec::new()
+    }
+
+    fn outputs(&self) -> Vec<OutputSpec> {
+        vec![OutputSpec::histogram("volume", "#26a69a")]
+    }
+
+    fn calculate(&self, data: &[CustomQuote], _params: &IndicatorParams) -> Vec<Vec<IndicatorData>> {
+        vec![calculate_volume(data)]
+    }
+}
+
+pub struct Obv;
+
+impl Indicator for Obv {
+    fn id(&self) -> &'static str {
+        "OBV"
+    }
+
+    fn name(&self) -> &'static str {
+        "On-Balance Volume"
+    }
+
+    fn pane(&self) -> Pane {
+        Pane::Separate
+    }
+
+    fn params(&self) -> Vec<ParamSpec> {
+        Vec::new()
+    }
+
+    fn outputs(&self) -> Vec<OutputSpec> {
+        vec![OutputSpec::line("obv", "#2962ff")]
+    }
+
+    fn calculate(&self, data: &[CustomQuote], _params: &IndicatorParams) -> Vec<Vec<IndicatorData>> {
+        vec![calculate_obv(data)]
+    }
+}
+
+pub struct AccumulationDistribution;
+
+impl Indicator for AccumulationDistribution {
+    fn id(&self) -> &'static str {
+        "AD"
+    }
+
+    fn name(&self) -> &'static str {
+        "Accumulation/Distribution"
+    }
+
+    fn pane(&self) -> Pane {
+        Pane::Separate
+    }
+
+    fn params(&self) -> Vec<ParamSpec> {
+        Vec::new()
+    }
+
+    fn outputs(&self) -> Vec<OutputSpec> {
+        vec![OutputSpec::line("ad", "#7e57c2")]
+    }
+
+    fn calculate(&self, data: &[CustomQuote], _params: &IndicatorParams) -> Vec<Vec<IndicatorData>> {
+        vec![calculate_accumulation_distribution(data)]
+    }
+}
+
+pub struct ChaikinMoneyFlow;
+
+impl Indicator for ChaikinMoneyFlow {
+    fn id(&self) -> &'static str {
+        "CMF"
+    }
+
+    fn name(&self) -> &'static str {
+        "Chaikin Money Flow"
+    }
+
+    fn pane(&self) -> Pane {
+        Pane::Separate
+    }
+
+    fn params(&self) -> Vec<ParamSpec> {
+        vec![ParamSpec::integer("period", "Period", 20, 1, 500)]
+    }
+
+    fn outputs(&self) -> Vec<OutputSpec> {
+        vec![OutputSpec::histogram("cmf", "#26a69a")]
+    }
+
+    fn calculate(&self, data: &[CustomQuote], params: &IndicatorParams) -> Vec<Vec<IndicatorData>> {
+        vec![calculate_chaikin_money_flow(data, param_usize(params, "period"))]
+    }
+}
+
+pub struct ChaikinOscillator;
+
+impl Indicator for ChaikinOscillator {
+    fn id(&self) -> &'static str {
+        "CHAIKIN"
+    }
+
+    fn name(&self) -> &'static str {
+        "Chaikin Oscillator"
+    }
+
+    fn pane(&self) -> Pane {
+        Pane::Separate
+    }
+
+    fn params(&self) -> Vec<ParamSpec> {
+        vec![
+            ParamSpec::integer("fast", "Fast EMA", 3, 1, 500),
+            ParamSpec::integer("slow", "Slow EMA", 10, 1, 500),
+        ]
+    }
+
+    fn outputs(&self) -> Vec<OutputSpec> {
+        vec![OutputSpec::line("chaikin", "#ef5350")]
+    }
+
+    fn calculate(&self, data: &[CustomQuote], params: &IndicatorParams) -> Vec<Vec<IndicatorData>> {
+        vec![calculate_chaikin_oscillator(
+            data,
+            param_usize(params, "fast"),
+            param_usize(params, "slow"),
+        )]
+    }
+}
+
+fn band_params() -> Vec<ParamSpec> {
+    vec![
+        ParamSpec::float("band1", "Inner band (std. dev.)", 1.0, 0.0, 10.0),
+        ParamSpec::float("band2", "Outer band (std. dev.)", 2.0, 0.0, 10.0),
+    ]
+}
+
+fn vwap_outputs() -> Vec<OutputSpec> {
+    vec![
+        OutputSpec::line("vwap", "#ff6d00"),
+        OutputSpec::line("upper_1", "#90caf9"),
+        OutputSpec::line("lower_1", "#90caf9"),
+        OutputSpec::line("upper_2", "#2962ff"),
+        OutputSpec::line("lower_2", "#2962ff"),
+    ]
+}
+
+fn vwap_series(data: &[CustomQuote], anchor: VwapAnchor, params: &IndicatorParams) -> Vec<Vec<IndicatorData>> {
+    let Vwap { vwap, mut upper, mut lower } = calculate_vwap(
+        data,
+        anchor,
+        &[param_f64(params, "band1"), param_f64(params, "band2")],
+    );
+    let (upper_2, lower_2) = (upper.pop().unwrap_or_default(), lower.pop().unwrap_or_default());
+    let (upper_1, lower_1) = (upper.pop().unwrap_or_default(), lower.pop().unwrap_or_default());
+    vec![vwap, upper_1, lower_1, upper_2, lower_2]
+}
+
+/// VWAP reset every session (0), week (1) or month (2).
+pub struct SessionVwap;
+
+impl Indicator for SessionVwap {
+    fn id(&self) -> &'static str {
+        "VWAP"
+    }
+
+    fn name(&self) -> &'static str {
+        "VWAP"
+    }
+
+    fn pane(&self) -> Pane {
+        Pane::Overlay
+    }
+
+    fn params(&self) -> Vec<ParamSpec> {
+        let mut params = vec![ParamSpec::integer("reset", "Reset (session/week/month)", 0, 0, 2)];
+        params.extend(band_params());
+        params
+    }
+
+    fn outputs(&self) -> Vec<OutputSpec> {
+        vwap_outputs()
+    }
+
+    fn presets(&self) -> Vec<Vec<f64>> {
+        vec![vec![0.0, 1.0, 2.0], vec![1.0, 1.0, 2.0], vec![2.0, 1.0, 2.0]]
+    }
+
+    fn calculate(&self, data: &[CustomQuote], params: &IndicatorParams) -> Vec<Vec<IndicatorData>> {
+        let anchor = match param_usize(params, "reset") {
+            0 => VwapAnchor::Session,
+            1 => VwapAnchor::Week,
+            _ => VwapAnchor::Month,
+        };
+        vwap_series(data, anchor, params)
+    }
+}
+
+/// VWAP accumulated from an anchor time given in Unix seconds; 0 anchors at the first bar.
+pub struct AnchoredVwap;
+
+impl Indicator for AnchoredVwap {
+    fn id(&self) -> &'static str {
+        "AVWAP"
+    }
+
+    fn name(&self) -> &'static str {
+        "Anchored VWAP"
+    }
+
+    fn pane(&self) -> Pane {
+        Pane::Overlay
+    }
+
+    fn params(&self) -> Vec<ParamSpec> {
+        let mut params = vec![ParamSpec::integer("anchor", "Anchor (Unix time)", 0, 0, 4_102_444_800)];
+        params.extend(band_params());
+        params
+    }
+
+    fn outputs(&self) -> Vec<OutputSpec> {
+        vwap_outputs()
+    }
+
+    fn calculate(&self, data: &[CustomQuote], params: &IndicatorParams) -> Vec<Vec<IndicatorData>> {
+        let anchor = Utc
+            .timestamp_opt(param_usize(params, "anchor") as i64, 0)
+            .single()
+            .unwrap_or_default();
+        vwap_series(data, VwapAnchor::Time(anchor), params)
+    }
+}
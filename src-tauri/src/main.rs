@@ -3,19 +3,36 @@
 pub mod utils;
 pub mod types;
 pub mod models;
+pub mod providers;
+pub mod strategies;
+pub mod indicators;
 use chrono::Duration;
 use lazy_static::lazy_static;
 use tokio::time::timeout;
-use utils::backtest_utils::calculate_performance;
+use utils::benchmark_utils::{aligned_closes, compare};
+use utils::backtest_utils::{calculate_performance, export_trades_csv, BacktestConfig};
 use std::time::Duration as TokioDuration;
-use utils::fetch_stock_utils::{fetch_stock_data, fetch_stock_data_for_backtest, filter_complete_quotes, transform_to_custom_quotes, FETCH_FAILED, MIN_DATE, ONGOING_REQUESTS};
-use utils::indicator_utils::{calculate_ema, calculate_macd, calculate_rsi, calculate_sma, calculate_volume};
+use utils::fetch_stock_utils::{fetch_stock_data, fetch_stock_data_for_backtest, filter_complete_quotes, parse_date, transform_to_custom_quotes, FETCH_FAILED, MIN_DATE, ONGOING_REQUESTS};
+use utils::indicator_utils::{calculate_volume_profile, VolumeProfile};
+use utils::adjust_utils::{fetch_adjusted_quotes, Adjustment};
+use utils::monte_carlo_utils::{run_monte_carlo, MonteCarloResult, MonteCarloSpec};
+use utils::portfolio_utils::{backtest_portfolio, PortfolioResult, PortfolioSpec, SymbolSeries};
+use utils::import_utils::{import_file, ImportOptions};
 use utils::init_data_utils::{fetch_initial_data, initialize_data};
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::{Arc, RwLock};
 use tauri::Manager;
-use types::{ CustomQuote, DateRange, IndicatorData, StockQuote, StrategyResult};
+use types::{ CorporateActions, CustomQuote, DateRange, IndicatorData, StockQuote, StrategyResult, Timeframe, Trade};
+use crate::models::bar_store::BarStore;
 use crate::models::stock_model::StockModel;
+use crate::providers::provider;
+use crate::indicators::{compute_indicator, find_indicator, IndicatorInfo, IndicatorParams, INDICATORS};
+use crate::strategies::optimizer::{optimize, OptimizationResult, OptimizationSpec};
+use crate::strategies::walk_forward::{walk_forward, WalkForwardResult, WalkForwardSpec};
+use crate::strategies::{find_strategy, generate_signals, StrategyInfo, StrategyParams, STRATEGIES};
+
+const BAR_CACHE_FILE: &str = "bars.sqlite";
 
 lazy_static! {
     static ref STOCK_DATA: Arc<RwLock<HashMap<String, StockModel>>> = {
@@ -29,66 +46,72 @@ lazy_static! {
     };
 }
 
+/// Creates the (symbol, timeframe) entry on first use of a non-default timeframe,
+/// filling it from cached lower timeframes when possible.
+fn ensure_timeframe(symbol: &str, timeframe: Timeframe) -> Result<(), String> {
+    let mut data_map = STOCK_DATA
+        .write()
+        .map_err(|_| "Failed to acquire lock on STOCK_DATA")?;
+    let stock_model = data_map.get_mut("stocks").ok_or("Stock data not found")?;
+    if stock_model.stock_datas.contains_key(symbol) {
+        stock_model.initialize_timeframe(symbol, timeframe);
+        stock_model.resample_timeframe(symbol, timeframe);
+    }
+    Ok(())
+}
+
+/// Calculates a registered indicator. Parameters may be given positionally in `values`
+/// (in declaration order, e.g. `[12, 26, 9]` for MACD) and/or by name in `params`.
 #[tauri::command]
 async fn get_indicators(
     symbol: &str,
-    timeframe: &str,
+    timeframe: Timeframe,
     variant: &str,
-    lengths: Vec<usize>, 
+    values: Option<Vec<f64>>,
+    params: Option<IndicatorParams>,
 ) -> Result<Vec<Vec<IndicatorData>>, String> {
-    let new_symbol = symbol.to_string();
-    let new_timeframe = timeframe.to_string();
-    let chart_data: Vec<CustomQuote> = get_data(new_symbol.clone(), new_timeframe.clone())
+    let indicator = find_indicator(variant)?;
+    let mut resolved = indicator.params_from_values(&values.unwrap_or_default())?;
+    resolved.extend(params.unwrap_or_default());
+
+    let chart_data: Vec<CustomQuote> = get_data(symbol.to_string(), timeframe).await?;
+    compute_indicator(indicator, &chart_data, timeframe, &resolved)
+}
+
+/// Price-by-volume profile of the loaded bars between `from` and `to` (the visible range).
+#[tauri::command]
+async fn get_volume_profile(
+    symbol: &str,
+    timeframe: Timeframe,
+    from: Option<String>,
+    to: Option<String>,
+    bins: Option<usize>,
+    value_area_percent: Option<f64>,
+) -> Result<VolumeProfile, String> {
+    let from = from.as_deref().map(parse_date).transpose()?;
+    let to = to.as_deref().map(parse_date).transpose()?;
+    let value_area_percent = value_area_percent.unwrap_or(70.0);
+    if !(0.0..=100.0).contains(&value_area_percent) {
+        return Err("value_area_percent must be between 0 and 100".to_string());
+    }
+
+    let visible: Vec<CustomQuote> = get_data(symbol.to_string(), timeframe)
         .await?
         .into_iter()
+        .filter(|quote| from.is_none_or(|from| quote.time >= from) && to.is_none_or(|to| quote.time <= to))
         .collect();
-
-    match variant {
-        "SMA" => {
-            if lengths.len() == 1 {
-                Ok(vec![calculate_sma(&chart_data, lengths[0])])
-            } else {
-                Err("SMA requires exactly one length".to_string())
-            }
-        },
-        "EMA" => {
-            if lengths.len() == 1 {
-                Ok(vec![calculate_ema(&chart_data, lengths[0])])
-            } else {
-                Err("EMA requires exactly one length".to_string())
-            }
-        },
-        "RSI" => {
-            if lengths.len() == 1 {
-                Ok(vec![calculate_rsi(&chart_data, lengths[0])])
-            } else {
-                Err("RSI requires exactly one length".to_string())
-            }
-        },
-        "MACD" => {
-            if lengths.len() == 3 {
-                let (macd_line, signal_line, macd_histogram) = calculate_macd(
-                    &chart_data,
-                    lengths[0],
-                    lengths[1],
-                    lengths[2],
-                );
-                Ok(vec![macd_line, signal_line, macd_histogram])
-            } else {
-                Err("MACD requires exactly three lengths".to_string())
-            }
-        },
-        "VOLUME" => {
-            Ok(vec![calculate_volume(&chart_data)])
-        },
-        _ => Err("Invalid indicator variant".to_string()),
-    }
+    Ok(calculate_volume_profile(&visible, bins.unwrap_or(24).clamp(1, 500), value_area_percent))
 }
 
+#[tauri::command]
+fn list_indicators() -> Vec<IndicatorInfo> {
+    INDICATORS.iter().map(|indicator| indicator.info()).collect()
+}
 
 #[tauri::command]
-async fn fetch_stock_chart(symbol: &str, timeframe: &str) -> Result<Vec<CustomQuote>, String> {
-    let key = (symbol.to_string(), timeframe.to_string());
+async fn fetch_stock_chart(symbol: &str, timeframe: Timeframe) -> Result<Vec<CustomQuote>, String> {
+    ensure_timeframe(symbol, timeframe)?;
+    let key = (symbol.to_string(), timeframe);
 
     let request_lock = {
         let mut ongoing_requests = ONGOING_REQUESTS.write().unwrap();
@@ -133,7 +156,7 @@ async fn fetch_stock_chart(symbol: &str, timeframe: &str) -> Result<Vec<CustomQu
         let stock_data = stock_model
             .stock_datas
             .get(symbol)
-            .and_then(|tf_map| tf_map.get(timeframe))
+            .and_then(|tf_map| tf_map.get(&timeframe))
             .ok_or("Stock data not found for the given key")?;
         (stock_data.from, stock_data.to)
     };
@@ -144,7 +167,7 @@ async fn fetch_stock_chart(symbol: &str, timeframe: &str) -> Result<Vec<CustomQu
             .map_err(|_| "Failed to acquire lock on STOCK_DATA")?;
         let stock_model = data_map.get_mut("stocks").ok_or("Stock data not found")?;
         let existing_quotes = stock_model
-            .get_data(&symbol, &timeframe)
+            .get_data(symbol, timeframe)
             .unwrap_or(&Vec::new())
             .clone();
 
@@ -157,15 +180,9 @@ async fn fetch_stock_chart(symbol: &str, timeframe: &str) -> Result<Vec<CustomQu
             })
             .collect();
 
-        stock_model.append_data(&symbol, &timeframe, new_quotes.clone());
+        stock_model.append_data(symbol, timeframe, new_quotes.clone());
 
-        let new_from = match timeframe {
-            "1M" => from - Duration::days(5),
-            "1H" => from - Duration::weeks(30),
-            "1D" => from - Duration::days(8 * 365),
-            "1WK" => from - Duration::days(15 * 365),
-            _ => return Err("Invalid timeframe".to_string()),
-        };
+        let new_from = from - timeframe.paging_step();
 
         let new_from = if new_from < *MIN_DATE {
             *MIN_DATE
@@ -184,8 +201,8 @@ async fn fetch_stock_chart(symbol: &str, timeframe: &str) -> Result<Vec<CustomQu
             from - Duration::days(1)
         };
 
-        stock_model.update_period(&symbol, &timeframe, new_from, from);
-        custom_quotes = stock_model.get_data(&symbol, &timeframe).unwrap().clone();
+        stock_model.update_period(symbol, timeframe, new_from, from);
+        custom_quotes = stock_model.get_data(symbol, timeframe).unwrap().clone();
 
         let mut times_seen = HashMap::new();
         let mut duplicates = Vec::new();
@@ -200,91 +217,186 @@ async fn fetch_stock_chart(symbol: &str, timeframe: &str) -> Result<Vec<CustomQu
         if !duplicates.is_empty() {
             for time in duplicates {
                 custom_quotes.retain(|quote| quote.time != time);
-                stock_model.remove_data(&symbol, &timeframe, time);
+                stock_model.remove_data(symbol, timeframe, time);
             }
         }
 
         custom_quotes.sort_by_key(|quote| quote.time);
-        stock_model.update_data(&symbol, &timeframe, custom_quotes.clone());
+        stock_model.update_data(symbol, timeframe, custom_quotes.clone());
     }
 
     Ok(custom_quotes)
 }
 
 
-#[tauri::command]
-async fn alligator_strategy(symbol: &str, timeframe: &str, from: &str, to:&str, period1: usize, period2: usize, period3: usize) -> Result<StrategyResult, String> {
-    let data = fetch_stock_data_for_backtest(symbol, timeframe, from, to).await.unwrap();
+/// Loads the complete bars of the backtest window with the requested adjustment applied.
+async fn load_backtest_quotes(
+    symbol: &str,
+    timeframe: Timeframe,
+    from: &str,
+    to: &str,
+    adjustment: Option<Adjustment>,
+) -> Result<Vec<CustomQuote>, String> {
+    let data = fetch_stock_data_for_backtest(symbol, timeframe, from, to).await?;
     let filtered_data = filter_complete_quotes(data);
     let custom_quotes = transform_to_custom_quotes(filtered_data);
-    let sma1 = calculate_sma(&custom_quotes, period1);
-    let sma2 = calculate_sma(&custom_quotes, period2);
-    let sma3 = calculate_sma(&custom_quotes, period3);
-
-    let mut signals = vec!["hold".to_string(); custom_quotes.len()];
-    for i in 1..custom_quotes.len() {
-        if sma1[i].value > sma2[i].value && sma2[i].value > sma3[i].value {
-            signals[i] = "buy".to_string();
-        } else if sma1[i].value < sma2[i].value && sma2[i].value < sma3[i].value {
-            signals[i] = "sell".to_string();
-        }
+    fetch_adjusted_quotes(symbol, custom_quotes, adjustment.unwrap_or_default()).await
+}
+
+/// Runs the strategy with `params` (missing parameters fall back to their defaults) and
+/// compares it with buy-and-hold and, if given, a `benchmark` symbol over the same window.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+async fn run_backtest(
+    strategy_id: &str,
+    params: Option<StrategyParams>,
+    symbol: &str,
+    timeframe: Timeframe,
+    from: &str,
+    to: &str,
+    adjustment: Option<Adjustment>,
+    config: Option<BacktestConfig>,
+    benchmark: Option<String>,
+) -> Result<StrategyResult, String> {
+    let strategy = find_strategy(strategy_id)?;
+    let params = params.unwrap_or_default();
+    let custom_quotes = load_backtest_quotes(symbol, timeframe, from, to, adjustment).await?;
+    let signals = generate_signals(strategy, &custom_quotes, &params)?;
+
+    let mut result = calculate_performance(&custom_quotes, &signals, timeframe, &config.unwrap_or_default());
+    result.buy_and_hold.symbol = symbol.to_string();
+    if let Some(benchmark) = benchmark {
+        let benchmark_quotes = load_backtest_quotes(&benchmark, timeframe, from, to, adjustment).await?;
+        let prices = aligned_closes(&custom_quotes[..result.equity_curve.len()], &benchmark_quotes);
+        result.benchmark = Some(compare(&result.equity_curve, &prices, timeframe, &benchmark));
     }
+    Ok(result)
+}
 
-    Ok(calculate_performance(&custom_quotes, &signals))
+/// Sweeps the strategy's parameters over one fetched dataset; the backtests run on the
+/// blocking pool so the async runtime stays responsive.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+async fn optimize_strategy(
+    strategy_id: &str,
+    symbol: &str,
+    timeframe: Timeframe,
+    from: &str,
+    to: &str,
+    adjustment: Option<Adjustment>,
+    config: Option<BacktestConfig>,
+    spec: OptimizationSpec,
+) -> Result<OptimizationResult, String> {
+    let strategy = find_strategy(strategy_id)?;
+    let custom_quotes = load_backtest_quotes(symbol, timeframe, from, to, adjustment).await?;
+    let config = config.unwrap_or_default();
+
+    tokio::task::spawn_blocking(move || optimize(strategy, &custom_quotes, timeframe, &config, &spec))
+        .await
+        .map_err(|e| e.to_string())?
 }
 
+/// Walk-forward test of the strategy over one fetched dataset, on the blocking pool.
 #[tauri::command]
-async fn macd_strategy(symbol: &str, timeframe: &str, from: &str, to:&str, macd_short: usize, macd_long: usize, macd_signal: usize) -> Result<StrategyResult, String> {
-    let data = fetch_stock_data_for_backtest(symbol, timeframe, from, to).await.unwrap();
-    let filtered_data = filter_complete_quotes(data);
-    let custom_quotes = transform_to_custom_quotes(filtered_data);
-    let (macd_line, signal_line, _) = calculate_macd(&custom_quotes, macd_short, macd_long, macd_signal);
+#[allow(clippy::too_many_arguments)]
+async fn walk_forward_strategy(
+    strategy_id: &str,
+    symbol: &str,
+    timeframe: Timeframe,
+    from: &str,
+    to: &str,
+    adjustment: Option<Adjustment>,
+    config: Option<BacktestConfig>,
+    spec: WalkForwardSpec,
+) -> Result<WalkForwardResult, String> {
+    let strategy = find_strategy(strategy_id)?;
+    let custom_quotes = load_backtest_quotes(symbol, timeframe, from, to, adjustment).await?;
+    let config = config.unwrap_or_default();
+
+    tokio::task::spawn_blocking(move || walk_forward(strategy, &custom_quotes, timeframe, &config, &spec))
+        .await
+        .map_err(|e| e.to_string())?
+}
 
-    let mut signals = vec!["hold".to_string(); custom_quotes.len()];
-    for i in 0..custom_quotes.len() {
-        if i < macd_short || i < macd_long || i < macd_signal {
-            continue;
-        }
-        if macd_line[i].value > signal_line[i].value {
-            signals[i] = "buy".to_string();
-        } else if  macd_line[i].value < signal_line[i].value {
-            signals[i] = "sell".to_string();
-        }
+/// Runs the strategy on every symbol of the basket and simulates them in one account.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+async fn portfolio_backtest(
+    strategy_id: &str,
+    params: Option<StrategyParams>,
+    symbols: Vec<String>,
+    timeframe: Timeframe,
+    from: &str,
+    to: &str,
+    adjustment: Option<Adjustment>,
+    config: Option<BacktestConfig>,
+    spec: Option<PortfolioSpec>,
+) -> Result<PortfolioResult, String> {
+    let strategy = find_strategy(strategy_id)?;
+    let params = params.unwrap_or_default();
+    let mut series = Vec::new();
+    for symbol in symbols {
+        let quotes = load_backtest_quotes(&symbol, timeframe, from, to, adjustment).await?;
+        let signals = generate_signals(strategy, &quotes, &params)?;
+        series.push(SymbolSeries { symbol, quotes, signals });
+    }
+    if series.is_empty() {
+        return Err("No symbols to backtest".to_string());
     }
 
-    Ok(calculate_performance(&custom_quotes, &signals))
+    Ok(backtest_portfolio(&series, &config.unwrap_or_default(), &spec.unwrap_or_default()))
 }
 
+/// Monte Carlo simulation of a finished backtest's trades or bar returns.
 #[tauri::command]
-async fn three_ema_strategy(symbol: &str, timeframe: &str, from: &str, to:&str, period1: usize, period2: usize, period3: usize) -> Result<StrategyResult, String> {
-    let data = fetch_stock_data_for_backtest(symbol, timeframe, from, to).await.unwrap();
-    let filtered_data = filter_complete_quotes(data);
-    let custom_quotes = transform_to_custom_quotes(filtered_data);
-    let ema1 = calculate_ema(&custom_quotes, period1);
-    let ema2 = calculate_ema(&custom_quotes, period2);
-    let ema3 = calculate_ema(&custom_quotes, period3);
-
-    let mut signals = vec!["hold".to_string(); custom_quotes.len()];
-    for i in 1..custom_quotes.len() {
-        if ema1[i].value > ema2[i].value && ema2[i].value > ema3[i].value {
-            signals[i] = "buy".to_string();
-        } else if ema1[i].value < ema2[i].value && ema2[i].value < ema3[i].value {
-            signals[i] = "sell".to_string();
-        }
-    }
+async fn monte_carlo_simulation(result: StrategyResult, spec: Option<MonteCarloSpec>) -> Result<MonteCarloResult, String> {
+    let spec = spec.unwrap_or_default();
+    tokio::task::spawn_blocking(move || run_monte_carlo(&result, &spec))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+fn list_strategies() -> Vec<StrategyInfo> {
+    STRATEGIES.iter().map(|strategy| strategy.info()).collect()
+}
+
+fn strategy_params(values: &[(&str, usize)]) -> StrategyParams {
+    values
+        .iter()
+        .map(|(name, value)| (name.to_string(), *value as f64))
+        .collect()
+}
+
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+async fn alligator_strategy(symbol: &str, timeframe: Timeframe, from: &str, to:&str, period1: usize, period2: usize, period3: usize, adjustment: Option<Adjustment>) -> Result<StrategyResult, String> {
+    let params = strategy_params(&[("period1", period1), ("period2", period2), ("period3", period3)]);
+    run_backtest("alligator", Some(params), symbol, timeframe, from, to, adjustment, None, None).await
+}
+
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+async fn macd_strategy(symbol: &str, timeframe: Timeframe, from: &str, to:&str, macd_short: usize, macd_long: usize, macd_signal: usize, adjustment: Option<Adjustment>) -> Result<StrategyResult, String> {
+    let params = strategy_params(&[("macd_short", macd_short), ("macd_long", macd_long), ("macd_signal", macd_signal)]);
+    run_backtest("macd", Some(params), symbol, timeframe, from, to, adjustment, None, None).await
+}
 
-    Ok(calculate_performance(&custom_quotes, &signals))
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+async fn three_ema_strategy(symbol: &str, timeframe: Timeframe, from: &str, to:&str, period1: usize, period2: usize, period3: usize, adjustment: Option<Adjustment>) -> Result<StrategyResult, String> {
+    let params = strategy_params(&[("period1", period1), ("period2", period2), ("period3", period3)]);
+    run_backtest("three_ema", Some(params), symbol, timeframe, from, to, adjustment, None, None).await
 }
 
 
 #[tauri::command]
 async fn add_item(item: String) -> Result<(), String> {
-    let timeframes = vec!["1M", "1H", "1D", "1WK"];
     let mut data = Vec::new();
 
-    for timeframe in timeframes.iter() {
+    for timeframe in Timeframe::DEFAULTS {
         if let Ok(data_item) = fetch_initial_data(&item, timeframe).await {
-            data.push((timeframe.to_string(), data_item));
+            data.push((timeframe, data_item));
         }
     }
 
@@ -298,7 +410,7 @@ async fn add_item(item: String) -> Result<(), String> {
     if !stock_model.stock_datas.contains_key(&item) {
         stock_model.initialize_timeframes(&item);
         for (timeframe, data_item) in data {
-            stock_model.append_data(&item, &timeframe, data_item);
+            stock_model.append_data(&item, timeframe, data_item);
         }
     }
 
@@ -306,7 +418,43 @@ async fn add_item(item: String) -> Result<(), String> {
 }
 
 #[tauri::command]
-async fn get_data(symbol: String, timeframe: String) -> Result<Vec<CustomQuote>, String> {
+fn export_trades(path: String, trades: Vec<Trade>) -> Result<(), String> {
+    export_trades_csv(&PathBuf::from(path), &trades)
+}
+
+#[tauri::command]
+async fn import_bars(
+    path: String,
+    symbol: String,
+    timeframe: Timeframe,
+    options: Option<ImportOptions>,
+) -> Result<usize, String> {
+    let quotes = import_file(&path, &options.unwrap_or_default())?;
+    if quotes.is_empty() {
+        return Err("No valid bars found in file".to_string());
+    }
+    let imported = quotes.len();
+
+    let mut data_map = STOCK_DATA
+        .write()
+        .map_err(|_| "Failed to acquire lock on STOCK_DATA")?;
+    let stock_model = data_map
+        .entry("stocks".to_string())
+        .or_insert_with(StockModel::new);
+    if !stock_model.stock_datas.contains_key(&symbol) {
+        stock_model.initialize_timeframes(&symbol);
+    }
+    stock_model.initialize_timeframe(&symbol, timeframe);
+    let (first, last) = (quotes[0].time, quotes[quotes.len() - 1].time);
+    stock_model.merge_data(&symbol, timeframe, quotes);
+    stock_model.cover_imported(&symbol, timeframe, first, last);
+
+    Ok(imported)
+}
+
+#[tauri::command]
+async fn get_data(symbol: String, timeframe: Timeframe) -> Result<Vec<CustomQuote>, String> {
+    ensure_timeframe(&symbol, timeframe)?;
     let data_map = STOCK_DATA
         .read()
         .map_err(|_| "Failed to acquire lock on STOCK_DATA")?;
@@ -321,7 +469,25 @@ async fn get_data(symbol: String, timeframe: String) -> Result<Vec<CustomQuote>,
 
 
 #[tauri::command]
-async fn get_range(symbol: String, timeframe: String) -> Result<DateRange, String> {
+async fn get_adjusted_data(
+    symbol: String,
+    timeframe: Timeframe,
+    adjustment: Adjustment,
+) -> Result<Vec<CustomQuote>, String> {
+    let quotes = get_data(symbol.clone(), timeframe).await?;
+    fetch_adjusted_quotes(&symbol, quotes, adjustment).await
+}
+
+#[tauri::command]
+async fn get_corporate_actions(symbol: String, from: &str, to: &str) -> Result<CorporateActions, String> {
+    provider()
+        .fetch_corporate_actions(&symbol, parse_date(from)?, parse_date(to)?)
+        .await
+}
+
+#[tauri::command]
+async fn get_range(symbol: String, timeframe: Timeframe) -> Result<DateRange, String> {
+    ensure_timeframe(&symbol, timeframe)?;
     let data_map = STOCK_DATA
         .read()
         .map_err(|_| "Failed to acquire lock on STOCK_DATA")?;
@@ -363,37 +529,35 @@ async fn delete_label(label: String) -> Result<(), String> {
         .write()
         .map_err(|_| "Failed to acquire lock on STOCK_DATA")?;
     let stock_model = data_map.get_mut("stocks").ok_or("Stock data not found")?;
-    stock_model.stock_datas.remove(&label);
+    stock_model.remove_symbol(&label);
     Ok(())
 }
 
 #[tauri::command]
 async fn search_indices(query: String) -> Result<Vec<String>, String> {
-    let client = reqwest::Client::new();
-    let url = format!("http://localhost:3000/search-stocks/{}", query);
+    provider().search_symbols(&query).await
+}
 
-    let response = client.get(&url).send().await;
-    if let Err(e) = response {
-        return Err(format!("Failed to fetch stock data: {}", e));
-    }
-    let response = response.unwrap();
-    if !response.status().is_success() {
-        return Err(format!(
-            "Failed to fetch stock data: HTTP {}",
-            response.status()
-        ));
-    }
-    let data: Vec<String> = response
-        .json()
-        .await
-        .map_err(|e| format!("Failed to parse response: {}", e))?;
-    Ok(data)
+fn attach_bar_store(path: PathBuf) -> Result<(), String> {
+    let store = BarStore::open(path)?;
+    let mut data_map = STOCK_DATA
+        .write()
+        .map_err(|_| "Failed to acquire lock on STOCK_DATA")?;
+    let stock_model = data_map.get_mut("stocks").ok_or("Stock data not found")?;
+    stock_model.attach_store(Arc::new(store))
 }
 
 #[tokio::main]
 async fn main() {
+    dotenv::dotenv().ok();
+
     tauri::Builder::default()
         .setup(|app| {
+            if let Some(cache_dir) = app.path_resolver().app_data_dir() {
+                if let Err(e) = attach_bar_store(cache_dir.join(BAR_CACHE_FILE)) {
+                    eprintln!("Failed to open bar cache: {}", e);
+                }
+            }
 
             tokio::task::block_in_place(|| {
                 let rt = tokio::runtime::Runtime::new().unwrap();
@@ -417,10 +581,22 @@ async fn main() {
             get_labels,
             search_indices,
             add_item,
+            import_bars,
+            export_trades,
             get_data,
+            get_adjusted_data,
+            get_corporate_actions,
             get_range,
             delete_label,
             get_indicators,
+            list_indicators,
+            get_volume_profile,
+            run_backtest,
+            optimize_strategy,
+            walk_forward_strategy,
+            monte_carlo_simulation,
+            portfolio_backtest,
+            list_strategies,
             macd_strategy,
             alligator_strategy,
             three_ema_strategy,
@@ -436,7 +612,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_alligator_strategy() {
-        let result = alligator_strategy("AAPL", "1D", "2020-01-01", "2020-12-31", 5, 8, 13).await;
+        let result = alligator_strategy("AAPL", Timeframe::OneDay, "2020-01-01", "2020-12-31", 5, 8, 13, None).await;
         assert!(result.is_ok());
         let strategy_result = result.unwrap();
         assert!(strategy_result.num_trades > 0);
@@ -444,7 +620,7 @@ mod tests {
     
     #[tokio::test]
     async fn test_macd_strategy() {
-        let result = macd_strategy("AAPL", "1D", "2020-01-01", "2020-12-31", 12, 26, 9).await;
+        let result = macd_strategy("AAPL", Timeframe::OneDay, "2020-01-01", "2020-12-31", 12, 26, 9, None).await;
         assert!(result.is_ok());
         let strategy_result = result.unwrap();
         assert!(strategy_result.num_trades > 0);
@@ -452,7 +628,7 @@ mod tests {
     
     #[tokio::test]
     async fn test_three_ema_strategy() {
-        let result = three_ema_strategy("AAPL", "1D", "2020-01-01", "2020-12-31", 5, 10, 20).await;
+        let result = three_ema_strategy("AAPL", Timeframe::OneDay, "2020-01-01", "2020-12-31", 5, 10, 20, None).await;
         assert!(result.is_ok());
         let strategy_result = result.unwrap();
         assert!(strategy_result.num_trades > 0);
@@ -0,0 +1,265 @@
+use std::path::Path;
+use std::sync::Mutex;
+
+use chrono::{DateTime, TimeZone, Utc};
+use rusqlite::{params, Connection};
+
+use crate::types::{CustomQuote, Timeframe};
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS bars (
+        symbol TEXT NOT NULL,
+        timeframe TEXT NOT NULL,
+        time INTEGER NOT NULL,
+        open REAL,
+        high REAL,
+        low REAL,
+        close REAL,
+        volume INTEGER,
+        PRIMARY KEY (symbol, timeframe, time)
+    );
+    CREATE TABLE IF NOT EXISTS coverage (
+        symbol TEXT NOT NULL,
+        timeframe TEXT NOT NULL,
+        period_from INTEGER NOT NULL,
+        period_to INTEGER NOT NULL,
+        PRIMARY KEY (symbol, timeframe)
+    );
+";
+
+pub type Period = (DateTime<Utc>, DateTime<Utc>);
+
+/// SQLite-backed cache of bars and the `from`/`to` window of each (symbol, timeframe).
+#[derive(Debug)]
+pub struct BarStore {
+    conn: Mutex<Connection>,
+}
+
+impl BarStore {
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        if let Some(parent) = path.as_ref().parent() {
+            std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        let conn = Connection::open(path).map_err(|e| e.to_string())?;
+        Self::with_connection(conn)
+    }
+
+    pub fn open_in_memory() -> Result<Self, String> {
+        let conn = Connection::open_in_memory().map_err(|e| e.to_string())?;
+        Self::with_connection(conn)
+    }
+
+    fn with_connection(conn: Connection) -> Result<Self, String> {
+        conn.execute_batch(SCHEMA).map_err(|e| e.to_string())?;
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    pub fn upsert_bars(&self, symbol: &str, timeframe: Timeframe, quotes: &[CustomQuote]) -> Result<(), String> {
+        let mut conn = self.conn.lock().map_err(|_| "Failed to acquire lock on bar store")?;
+        let tx = conn.transaction().map_err(|e| e.to_string())?;
+        insert_bars(&tx, symbol, timeframe, quotes)?;
+        tx.commit().map_err(|e| e.to_string())
+    }
+
+    pub fn replace_bars(&self, symbol: &str, timeframe: Timeframe, quotes: &[CustomQuote]) -> Result<(), String> {
+        let mut conn = self.conn.lock().map_err(|_| "Failed to acquire lock on bar store")?;
+        let tx = conn.transaction().map_err(|e| e.to_string())?;
+        tx.execute(
+            "DELETE FROM bars WHERE symbol = ?1 AND timeframe = ?2",
+            params![symbol, timeframe.as_str()],
+        )
+        .map_err(|e| e.to_string())?;
+        insert_bars(&tx, symbol, timeframe, quotes)?;
+        tx.commit().map_err(|e| e.to_string())
+    }
+
+    pub fn remove_bar(&self, symbol: &str, timeframe: Timeframe, time: DateTime<Utc>) -> Result<(), String> {
+        let conn = self.conn.lock().map_err(|_| "Failed to acquire lock on bar store")?;
+        conn.execute(
+            "DELETE FROM bars WHERE symbol = ?1 AND timeframe = ?2 AND time = ?3",
+            params![symbol, timeframe.as_str(), time.timestamp()],
+        )
+        .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+
+    pub fn load_bars(&self, symbol: &str, timeframe: Timeframe) -> Result<Vec<CustomQuote>, String> {
+        let conn = self.conn.lock().map_err(|_| "Failed to acquire lock on bar store")?;
+        let mut stmt = conn
+            .prepare(
+                "SELECT time, open, high, low, close, volume FROM bars
+                 WHERE symbol = ?1 AND timeframe = ?2 ORDER BY time",
+            )
+            .map_err(|e| e.to_string())?;
+        let rows = stmt
+            .query_map(params![symbol, timeframe.as_str()], |row| {
+                Ok(CustomQuote {
+                    time: from_timestamp(row.get(0)?),
+                    open: row.get(1)?,
+                    high: row.get(2)?,
+                    low: row.get(3)?,
+                    close: row.get(4)?,
+                    volume: row.get::<_, Option<i64>>(5)?.map(|volume| volume as u64),
+                })
+            })
+            .map_err(|e| e.to_string())?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(|e| e.to_string())
+    }
+
+    pub fn save_period(
+        &self,
+        symbol: &str,
+        timeframe: Timeframe,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<(), String> {
+        let conn = self.conn.lock().map_err(|_| "Failed to acquire lock on bar store")?;
+        conn.execute(
+            "INSERT OR REPLACE INTO coverage (symbol, timeframe, period_from, period_to)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![symbol, timeframe.as_str(), from.timestamp(), to.timestamp()],
+        )
+        .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+
+    pub fn load_period(
+        &self,
+        symbol: &str,
+        timeframe: Timeframe,
+    ) -> Result<Option<Period>, String> {
+        let conn = self.conn.lock().map_err(|_| "Failed to acquire lock on bar store")?;
+        let mut stmt = conn
+            .prepare("SELECT period_from, period_to FROM coverage WHERE symbol = ?1 AND timeframe = ?2")
+            .map_err(|e| e.to_string())?;
+        let mut rows = stmt
+            .query_map(params![symbol, timeframe.as_str()], |row| {
+                Ok((from_timestamp(row.get(0)?), from_timestamp(row.get(1)?)))
+            })
+            .map_err(|e| e.to_string())?;
+        rows.next().transpose().map_err(|e| e.to_string())
+    }
+
+    /// Every (symbol, timeframe) pair that has bars or a saved window.
+    pub fn keys(&self) -> Result<Vec<(String, Timeframe)>, String> {
+        let conn = self.conn.lock().map_err(|_| "Failed to acquire lock on bar store")?;
+        let mut stmt = conn
+            .prepare(
+                "SELECT symbol, timeframe FROM bars
+                 UNION SELECT symbol, timeframe FROM coverage
+                 ORDER BY symbol, timeframe",
+            )
+            .map_err(|e| e.to_string())?;
+        let rows = stmt
+            .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))
+            .map_err(|e| e.to_string())?;
+        let mut keys = Vec::new();
+        for row in rows {
+            let (symbol, timeframe) = row.map_err(|e| e.to_string())?;
+            keys.push((symbol, timeframe.parse()?));
+        }
+        keys.sort();
+        Ok(keys)
+    }
+
+    pub fn remove_symbol(&self, symbol: &str) -> Result<(), String> {
+        let conn = self.conn.lock().map_err(|_| "Failed to acquire lock on bar store")?;
+        conn.execute("DELETE FROM bars WHERE symbol = ?1", params![symbol])
+            .map_err(|e| e.to_string())?;
+        conn.execute("DELETE FROM coverage WHERE symbol = ?1", params![symbol])
+            .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+}
+
+fn insert_bars(conn: &Connection, symbol: &str, timeframe: Timeframe, quotes: &[CustomQuote]) -> Result<(), String> {
+    let mut stmt = conn
+        .prepare(
+            "INSERT OR REPLACE INTO bars (symbol, timeframe, time, open, high, low, close, volume)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        )
+        .map_err(|e| e.to_string())?;
+    for quote in quotes {
+        stmt.execute(params![
+            symbol,
+            timeframe.as_str(),
+            quote.time.timestamp(),
+            quote.open,
+            quote.high,
+            quote.low,
+            quote.close,
+            quote.volume.map(|volume| volume as i64),
+        ])
+        .map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+fn from_timestamp(seconds: i64) -> DateTime<Utc> {
+    Utc.timestamp_opt(seconds, 0).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn quote(time: DateTime<Utc>, close: f64) -> CustomQuote {
+        CustomQuote {
+            high: Some(close + 1.0),
+            volume: Some(1000),
+            open: Some(close),
+            low: Some(close - 1.0),
+            close: Some(close),
+            time,
+        }
+    }
+
+    #[test]
+    fn test_upsert_and_load_bars() {
+        let store = BarStore::open_in_memory().unwrap();
+        let start = Utc.with_ymd_and_hms(2022, 1, 3, 0, 0, 0).unwrap();
+        let quotes = vec![quote(start + Duration::days(1), 11.0), quote(start, 10.0)];
+        store.upsert_bars("AAPL", Timeframe::OneDay, &quotes).unwrap();
+        store.upsert_bars("AAPL", Timeframe::OneDay, &[quote(start, 12.0)]).unwrap();
+
+        let loaded = store.load_bars("AAPL", Timeframe::OneDay).unwrap();
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded[0].time, start);
+        assert_eq!(loaded[0].close, Some(12.0));
+        assert!(store.load_bars("AAPL", Timeframe::OneHour).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_replace_and_remove_bars() {
+        let store = BarStore::open_in_memory().unwrap();
+        let start = Utc.with_ymd_and_hms(2022, 1, 3, 0, 0, 0).unwrap();
+        store.upsert_bars("AAPL", Timeframe::OneDay, &[quote(start, 10.0)]).unwrap();
+        let replacement = vec![quote(start + Duration::days(1), 11.0), quote(start + Duration::days(2), 12.0)];
+        store.replace_bars("AAPL", Timeframe::OneDay, &replacement).unwrap();
+        store.remove_bar("AAPL", Timeframe::OneDay, start + Duration::days(2)).unwrap();
+
+        assert_eq!(store.load_bars("AAPL", Timeframe::OneDay).unwrap(), vec![replacement[0].clone()]);
+    }
+
+    #[test]
+    fn test_period_and_keys() {
+        let store = BarStore::open_in_memory().unwrap();
+        let from = Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap();
+        let to = Utc.with_ymd_and_hms(2021, 1, 1, 0, 0, 0).unwrap();
+        store.save_period("AAPL", Timeframe::OneWeek, from, to).unwrap();
+        store.upsert_bars("MSFT", Timeframe::OneDay, &[quote(to, 5.0)]).unwrap();
+
+        assert_eq!(store.load_period("AAPL", Timeframe::OneWeek).unwrap(), Some((from, to)));
+        assert_eq!(store.load_period("AAPL", Timeframe::OneDay).unwrap(), None);
+        assert_eq!(
+            store.keys().unwrap(),
+            vec![("AAPL".to_string(), Timeframe::OneWeek), ("MSFT".to_string(), Timeframe::OneDay)]
+        );
+
+        store.remove_symbol("AAPL").unwrap();
+        assert_eq!(store.keys().unwrap().len(), 1);
+    }
+}
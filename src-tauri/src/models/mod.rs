@@ -1,2 +1,3 @@
+pub mod bar_store;
 pub mod stock_data;
 pub mod stock_model;
\ No newline at end of file
@@ -1,3 +1,5 @@
+use std::collections::HashSet;
+
 use chrono::{DateTime, Utc};
 
 use crate::types::CustomQuote;
@@ -8,6 +10,9 @@ pub struct StockData {
     pub chart_data: Vec<CustomQuote>,
     pub from: DateTime<Utc>,
     pub to: DateTime<Utc>,
+    /// Times of bars resampled locally from another timeframe rather than fetched. They
+    /// are kept in memory only and give way to fetched bars covering the same span.
+    pub derived: HashSet<DateTime<Utc>>,
 }
 
 impl StockData {
@@ -16,6 +21,7 @@ impl StockData {
             chart_data: Vec::new(),
             from,
             to,
+            derived: HashSet::new(),
         }
     }
 
@@ -1,75 +1,183 @@
-    use std::collections::HashMap;
+    use std::collections::{HashMap, HashSet};
+    use std::sync::Arc;
 
     use chrono::{DateTime, Duration, TimeZone, Utc};
 
-    use crate::types::CustomQuote;
+    use crate::types::{CustomQuote, Timeframe};
+    use crate::models::bar_store::BarStore;
     use crate::models::stock_data::StockData;
+    use crate::utils::fetch_stock_utils::MIN_DATE;
+    use crate::utils::resample_utils::{best_source, resample};
 
 
     #[derive(Debug)]
     pub struct StockModel {
-        pub stock_datas: HashMap<String, HashMap<String, StockData>>,
+        pub stock_datas: HashMap<String, HashMap<Timeframe, StockData>>,
+        store: Option<Arc<BarStore>>,
     }
 
     impl StockModel {
         pub fn new() -> Self {
             Self {
                 stock_datas: HashMap::new(),
+                store: None,
+            }
+        }
+
+        /// Attaches a persistent cache: everything already in it is loaded into memory
+        /// (overriding the default windows), and later changes are written through.
+        pub fn attach_store(&mut self, store: Arc<BarStore>) -> Result<(), String> {
+            for (symbol, interval) in store.keys()? {
+                if !self.stock_datas.contains_key(&symbol) {
+                    self.initialize_timeframes(&symbol);
+                }
+                let bars = store.load_bars(&symbol, interval)?;
+                let period = store.load_period(&symbol, interval)?;
+                let stock_data = self.initialize_timeframe(&symbol, interval);
+                stock_data.chart_data = bars;
+                if let Some((from, to)) = period {
+                    stock_data.update_period(from, to);
+                }
+            }
+
+            for (symbol, timeframe_map) in &self.stock_datas {
+                for (interval, stock_data) in timeframe_map {
+                    if store.load_period(symbol, *interval)?.is_none() {
+                        store.save_period(symbol, *interval, stock_data.from, stock_data.to)?;
+                    }
+                }
+            }
+
+            self.store = Some(store);
+            Ok(())
+        }
+
+        fn write_through<F>(&self, write: F)
+        where
+            F: FnOnce(&BarStore) -> Result<(), String>,
+        {
+            if let Some(store) = &self.store {
+                if let Err(e) = write(store) {
+                    eprintln!("Failed to write to bar cache: {}", e);
+                }
             }
         }
 
         pub fn initialize_timeframes(&mut self, symbol: &str) {
+            self.stock_datas.insert(symbol.to_string(), HashMap::new());
+            for timeframe in Timeframe::DEFAULTS {
+                self.initialize_timeframe(symbol, timeframe);
+            }
+        }
+
+        /// Returns the symbol's data for `timeframe`, creating it with the default
+        /// window (clamped to 2000-01-01) if the timeframe hasn't been used yet.
+        pub fn initialize_timeframe(&mut self, symbol: &str, timeframe: Timeframe) -> &mut StockData {
             let now = Utc::now();
             let min_date = Utc.with_ymd_and_hms(2000, 1, 1, 0, 0, 0).unwrap();
+            let from = now - timeframe.initial_lookback();
+            let adjusted_from = if from < min_date { min_date } else { from };
 
-            let timeframes = vec![
-                ("1M", now - Duration::days(3)),
-                ("1H", now - Duration::weeks(15)),
-                ("1D", now - Duration::days(4 * 365)),
-                ("1WK", now - Duration::days(7 * 365)),
-            ];
-
-            let mut timeframe_map = HashMap::new();
-            for (interval, from) in timeframes {
-                let adjusted_from = if from < min_date { min_date } else { from };
-                timeframe_map.insert(interval.to_string(), StockData::new(adjusted_from, now));
-            }
-
-            self.stock_datas.insert(symbol.to_string(), timeframe_map);
+            let store = self.store.clone();
+            self.stock_datas
+                .entry(symbol.to_string())
+                .or_default()
+                .entry(timeframe)
+                .or_insert_with(|| {
+                    if let Some(store) = store {
+                        if let Err(e) = store.save_period(symbol, timeframe, adjusted_from, now) {
+                            eprintln!("Failed to write to bar cache: {}", e);
+                        }
+                    }
+                    StockData::new(adjusted_from, now)
+                })
         }
 
-        pub fn get_data(&self, symbol: &str, interval: &str) -> Option<&Vec<CustomQuote>> {
+        pub fn get_data(&self, symbol: &str, interval: Timeframe) -> Option<&Vec<CustomQuote>> {
             self.stock_datas
                 .get(symbol)
-                .and_then(|timeframe_map| timeframe_map.get(interval))
+                .and_then(|timeframe_map| timeframe_map.get(&interval))
                 .map(|stock_data| &stock_data.chart_data)
         }
 
+        pub fn latest_time(&self, symbol: &str, interval: Timeframe) -> Option<DateTime<Utc>> {
+            self.get_data(symbol, interval)
+                .and_then(|quotes| quotes.iter().map(|quote| quote.time).max())
+        }
+
         pub fn update_period(
             &mut self,
             symbol: &str,
-            interval: &str,
+            interval: Timeframe,
             new_from: DateTime<Utc>,
             new_to: DateTime<Utc>,
         ) {
             if let Some(timeframe_map) = self.stock_datas.get_mut(symbol) {
-                if let Some(stock_data) = timeframe_map.get_mut(interval) {
+                if let Some(stock_data) = timeframe_map.get_mut(&interval) {
                     stock_data.update_period(new_from, new_to);
                 }
             }
+            self.write_through(|store| store.save_period(symbol, interval, new_from, new_to));
+        }
+
+        /// The period is the next window paged in backwards, ending just before the earliest
+        /// loaded bar. When imported bars `first..=last` already cover that window's end, it
+        /// moves below the import so paging doesn't fetch the imported span again.
+        pub fn cover_imported(&mut self, symbol: &str, interval: Timeframe, first: DateTime<Utc>, last: DateTime<Utc>) {
+            let Some(stock_data) = self.stock_datas.get(symbol).and_then(|tf_map| tf_map.get(&interval)) else {
+                return;
+            };
+            if stock_data.to < first || stock_data.to > interval.next_bar_time(last) {
+                return;
+            }
+            let to = (first - Duration::days(1)).max(*MIN_DATE);
+            let from = (to - interval.paging_step()).max(*MIN_DATE);
+            self.update_period(symbol, interval, from, to);
         }
 
-        pub fn remove_data(&mut self, symbol: &str, interval: &str, time: DateTime<Utc>) {
+        /// Loaded bars in `[from, to]`, only if nothing in that range is left to fetch: the
+        /// period has paged back past `from` (or a bar exists at or before it) and the last bar
+        /// reaches `to`, or now when `to` is in the future.
+        pub fn covered_data(
+            &self,
+            symbol: &str,
+            interval: Timeframe,
+            from: DateTime<Utc>,
+            to: DateTime<Utc>,
+        ) -> Option<Vec<CustomQuote>> {
+            let stock_data = self.stock_datas.get(symbol)?.get(&interval)?;
+            let (first, last) = (stock_data.chart_data.first()?, stock_data.chart_data.last()?);
+            let start_covered = stock_data.to <= from || first.time <= from;
+            let end_covered = interval.next_bar_time(last.time) >= to.min(Utc::now());
+            (start_covered && end_covered).then(|| {
+                stock_data
+                    .chart_data
+                    .iter()
+                    .filter(|quote| quote.time >= from && quote.time <= to)
+                    .cloned()
+                    .collect()
+            })
+        }
+
+        pub fn remove_data(&mut self, symbol: &str, interval: Timeframe, time: DateTime<Utc>) {
             if let Some(timeframe_map) = self.stock_datas.get_mut(symbol) {
-                if let Some(stock_data) = timeframe_map.get_mut(interval) {
+                if let Some(stock_data) = timeframe_map.get_mut(&interval) {
                     stock_data.chart_data.retain(|quote| quote.time != time);
+                    stock_data.derived.remove(&time);
                 }
             }
+            self.write_through(|store| store.remove_bar(symbol, interval, time));
+        }
+
+        pub fn remove_symbol(&mut self, symbol: &str) {
+            self.stock_datas.remove(symbol);
+            self.write_through(|store| store.remove_symbol(symbol));
         }
 
-        pub fn append_data(&mut self, symbol: &str, interval: &str, new_data: Vec<CustomQuote>) {
+        pub fn append_data(&mut self, symbol: &str, interval: Timeframe, new_data: Vec<CustomQuote>) {
+            self.replace_derived(symbol, interval, &new_data);
             if let Some(timeframe_map) = self.stock_datas.get_mut(symbol) {
-                if let Some(stock_data) = timeframe_map.get_mut(interval) {
+                if let Some(stock_data) = timeframe_map.get_mut(&interval) {
                     let existing_data = &mut stock_data.chart_data;
 
                     let mut filtered_new_data: Vec<CustomQuote> = Vec::new();
@@ -83,6 +191,11 @@
                     }
 
                     if !filtered_new_data.is_empty() {
+                        if let Some(store) = &self.store {
+                            if let Err(e) = store.upsert_bars(symbol, interval, &filtered_new_data) {
+                                eprintln!("Failed to write to bar cache: {}", e);
+                            }
+                        }
                         filtered_new_data.extend(existing_data.clone());
                         existing_data.clear();
                         existing_data.extend(filtered_new_data);
@@ -91,13 +204,84 @@
             }
         }
 
-        pub fn update_data(&mut self, symbol: &str, interval: &str, updated_data: Vec<CustomQuote>) {
+        /// Merges `quotes` into the series, replacing bars with the same time and keeping it sorted.
+        pub fn merge_data(&mut self, symbol: &str, interval: Timeframe, quotes: Vec<CustomQuote>) {
+            self.replace_derived(symbol, interval, &quotes);
+            let new_times: HashSet<DateTime<Utc>> = quotes.iter().map(|quote| quote.time).collect();
+            let mut merged = self.get_data(symbol, interval).cloned().unwrap_or_default();
+            merged.retain(|quote| !new_times.contains(&quote.time));
+            merged.extend(quotes);
+            merged.sort_by_key(|quote| quote.time);
+            self.update_data(symbol, interval, merged);
+        }
+
+        /// Drops resampled bars that fall within the span of fetched or imported `quotes`. A
+        /// resampled bar is stamped at its first source bar while providers stamp the period
+        /// start, so the two would otherwise both survive as overlapping bars.
+        fn replace_derived(&mut self, symbol: &str, interval: Timeframe, quotes: &[CustomQuote]) {
+            let (Some(first), Some(last)) = (
+                quotes.iter().map(|quote| quote.time).min(),
+                quotes.iter().map(|quote| quote.time).max(),
+            ) else {
+                return;
+            };
+            let Some(stock_data) = self.stock_datas.get_mut(symbol).and_then(|tf_map| tf_map.get_mut(&interval)) else {
+                return;
+            };
+            if stock_data.derived.is_empty() {
+                return;
+            }
+            let end = interval.next_bar_time(last);
+            let replaced: HashSet<DateTime<Utc>> = stock_data
+                .derived
+                .iter()
+                .copied()
+                .filter(|time| *time >= first && *time < end)
+                .collect();
+            stock_data.chart_data.retain(|quote| !replaced.contains(&quote.time));
+            stock_data.derived.retain(|time| !replaced.contains(time));
+        }
+
+        /// Fills an empty timeframe by resampling the coarsest cached timeframe that nests
+        /// into it. The bars are marked as derived and never written to the bar cache.
+        /// Returns the timeframe used, if any.
+        pub fn resample_timeframe(&mut self, symbol: &str, interval: Timeframe) -> Option<Timeframe> {
+            let timeframe_map = self.stock_datas.get(symbol)?;
+            if !timeframe_map.get(&interval)?.chart_data.is_empty() {
+                return None;
+            }
+            let available: Vec<Timeframe> = timeframe_map
+                .iter()
+                .filter(|(_, stock_data)| !stock_data.chart_data.is_empty())
+                .map(|(timeframe, _)| *timeframe)
+                .collect();
+            let source = best_source(interval, &available)?;
+
+            let quotes = resample(&timeframe_map[&source].chart_data, interval);
+            let stock_data = self.stock_datas.get_mut(symbol)?.get_mut(&interval)?;
+            stock_data.derived = quotes.iter().map(|quote| quote.time).collect();
+            stock_data.chart_data = quotes;
+            Some(source)
+        }
+
+        pub fn update_data(&mut self, symbol: &str, interval: Timeframe, updated_data: Vec<CustomQuote>) {
             if let Some(timeframe_map) = self.stock_datas.get_mut(symbol) {
-                if let Some(stock_data) = timeframe_map.get_mut(interval) {
+                if let Some(stock_data) = timeframe_map.get_mut(&interval) {
                     stock_data.chart_data.clear();
                     stock_data.chart_data.extend(updated_data);
+                    let times: HashSet<DateTime<Utc>> = stock_data.chart_data.iter().map(|quote| quote.time).collect();
+                    stock_data.derived.retain(|time| times.contains(time));
                 }
             }
+            if let Some(stock_data) = self.stock_datas.get(symbol).and_then(|tf_map| tf_map.get(&interval)) {
+                let fetched: Vec<CustomQuote> = stock_data
+                    .chart_data
+                    .iter()
+                    .filter(|quote| !stock_data.derived.contains(&quote.time))
+                    .cloned()
+                    .collect();
+                self.write_through(|store| store.replace_bars(symbol, interval, &fetched));
+            }
         }
     }
 
@@ -118,18 +302,144 @@
             stock_model.initialize_timeframes("AAPL");
     
             let timeframes = stock_model.stock_datas.get("AAPL").unwrap();
-            assert!(timeframes.contains_key("1M"));
-            assert!(timeframes.contains_key("1H"));
-            assert!(timeframes.contains_key("1D"));
-            assert!(timeframes.contains_key("1WK"));
+            assert!(timeframes.contains_key(&Timeframe::OneMinute));
+            assert!(timeframes.contains_key(&Timeframe::OneHour));
+            assert!(timeframes.contains_key(&Timeframe::OneDay));
+            assert!(timeframes.contains_key(&Timeframe::OneWeek));
         }
     
         #[test]
         fn test_get_data() {
             let mut stock_model = StockModel::new();
             stock_model.initialize_timeframes("AAPL");
-            let data = stock_model.get_data("AAPL", "1M");
+            let data = stock_model.get_data("AAPL", Timeframe::OneMinute);
             assert!(data.is_some());
         }
+
+        #[test]
+        fn test_resample_timeframe() {
+            let mut stock_model = StockModel::new();
+            stock_model.initialize_timeframes("AAPL");
+            let start = Utc.with_ymd_and_hms(2024, 1, 8, 14, 30, 0).unwrap();
+            let quotes: Vec<CustomQuote> = (0..5)
+                .map(|day| CustomQuote {
+                    high: Some(11.0),
+                    volume: Some(100),
+                    open: Some(10.0),
+                    low: Some(9.0),
+                    close: Some(10.0 + day as f64),
+                    time: start + chrono::Duration::days(day),
+                })
+                .collect();
+            stock_model.update_data("AAPL", Timeframe::OneDay, quotes);
+            stock_model.initialize_timeframe("AAPL", Timeframe::OneMonth);
+
+            assert_eq!(stock_model.resample_timeframe("AAPL", Timeframe::OneMonth), Some(Timeframe::OneDay));
+            let monthly = stock_model.get_data("AAPL", Timeframe::OneMonth).unwrap();
+            assert_eq!(monthly.len(), 1);
+            assert_eq!(monthly[0].volume, Some(500));
+            assert_eq!(monthly[0].close, Some(14.0));
+            assert_eq!(stock_model.resample_timeframe("AAPL", Timeframe::FiveMinutes), None);
+        }
+
+        #[test]
+        fn test_fetched_bars_replace_resampled_span() {
+            let store = Arc::new(BarStore::open_in_memory().unwrap());
+            let mut stock_model = StockModel::new();
+            stock_model.attach_store(store.clone()).unwrap();
+            stock_model.initialize_timeframes("AAPL");
+            let bar = |time: DateTime<Utc>, close: f64| CustomQuote {
+                high: Some(close + 1.0),
+                volume: Some(100),
+                open: Some(close),
+                low: Some(close - 1.0),
+                close: Some(close),
+                time,
+            };
+            let daily: Vec<CustomQuote> = [(2023, 12, 28), (2023, 12, 29), (2024, 1, 2), (2024, 1, 3)]
+                .iter()
+                .map(|&(year, month, day)| bar(Utc.with_ymd_and_hms(year, month, day, 0, 0, 0).unwrap(), 10.0))
+                .collect();
+            stock_model.update_data("AAPL", Timeframe::OneDay, daily.clone());
+            stock_model.initialize_timeframe("AAPL", Timeframe::OneMonth);
+            stock_model.resample_timeframe("AAPL", Timeframe::OneMonth);
+            assert_eq!(stock_model.get_data("AAPL", Timeframe::OneMonth).unwrap().len(), 2);
+            assert!(store.load_bars("AAPL", Timeframe::OneMonth).unwrap().is_empty());
+
+            // The provider stamps January at the 1st; the resampled bar starts on the 2nd.
+            let january = bar(Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(), 12.0);
+            stock_model.append_data("AAPL", Timeframe::OneMonth, vec![january.clone()]);
+            let mut monthly = stock_model.get_data("AAPL", Timeframe::OneMonth).unwrap().clone();
+            monthly.sort_by_key(|quote| quote.time);
+            assert_eq!(monthly.len(), 2);
+            assert_eq!(monthly[0].time, daily[0].time);
+            assert_eq!(monthly[1], january);
+
+            stock_model.merge_data("AAPL", Timeframe::OneMonth, vec![bar(daily[0].time, 11.0)]);
+            assert_eq!(store.load_bars("AAPL", Timeframe::OneMonth).unwrap().len(), 2);
+            assert!(stock_model.stock_datas["AAPL"][&Timeframe::OneMonth].derived.is_empty());
+        }
+
+        #[test]
+        fn test_store_write_through_and_reload() {
+            let store = Arc::new(BarStore::open_in_memory().unwrap());
+            let mut stock_model = StockModel::new();
+            stock_model.attach_store(store.clone()).unwrap();
+            stock_model.initialize_timeframes("AAPL");
+
+            let time = Utc.with_ymd_and_hms(2022, 1, 3, 0, 0, 0).unwrap();
+            let quote = CustomQuote {
+                high: Some(11.0),
+                volume: Some(100),
+                open: Some(10.0),
+                low: Some(9.0),
+                close: Some(10.5),
+                time,
+            };
+            stock_model.append_data("AAPL", Timeframe::OneDay, vec![quote.clone()]);
+            let from = Utc.with_ymd_and_hms(2015, 1, 1, 0, 0, 0).unwrap();
+            stock_model.update_period("AAPL", Timeframe::OneDay, from, time);
+
+            let mut reloaded = StockModel::new();
+            reloaded.attach_store(store.clone()).unwrap();
+            assert_eq!(reloaded.get_data("AAPL", Timeframe::OneDay), Some(&vec![quote]));
+            assert_eq!(reloaded.stock_datas["AAPL"][&Timeframe::OneDay].from, from);
+            assert_eq!(reloaded.latest_time("AAPL", Timeframe::OneDay), Some(time));
+
+            reloaded.remove_symbol("AAPL");
+            assert!(store.keys().unwrap().is_empty());
+        }
+
+        #[test]
+        fn test_imported_bars_extend_coverage() {
+            let mut stock_model = StockModel::new();
+            stock_model.initialize_timeframes("AAPL");
+            let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+            let quotes: Vec<CustomQuote> = (0..10)
+                .map(|day| CustomQuote {
+                    high: Some(11.0),
+                    volume: Some(100),
+                    open: Some(10.0),
+                    low: Some(9.0),
+                    close: Some(10.0),
+                    time: start + Duration::days(day),
+                })
+                .collect();
+            let (first, last) = (quotes[0].time, quotes[9].time);
+            stock_model.merge_data("AAPL", Timeframe::OneDay, quotes);
+            stock_model.update_period("AAPL", Timeframe::OneDay, start, last);
+            let range = (start + Duration::days(2), start + Duration::days(5));
+            assert!(stock_model.covered_data("AAPL", Timeframe::OneDay, range.0, range.1).is_some());
+            assert!(stock_model.covered_data("AAPL", Timeframe::OneDay, start - Duration::days(1), range.1).is_none());
+
+            stock_model.cover_imported("AAPL", Timeframe::OneDay, first, last);
+            let stock_data = &stock_model.stock_datas["AAPL"][&Timeframe::OneDay];
+            assert_eq!(stock_data.to, first - Duration::days(1));
+            assert_eq!(stock_data.from, stock_data.to - Timeframe::OneDay.paging_step());
+            assert_eq!(
+                stock_model.covered_data("AAPL", Timeframe::OneDay, start - Duration::days(1), range.1).map(|bars| bars.len()),
+                Some(6)
+            );
+        }
     }
     
\ No newline at end of file
@@ -0,0 +1,154 @@
+use async_trait::async_trait;
+use chrono::{DateTime, SecondsFormat, Utc};
+use reqwest::StatusCode;
+
+use crate::types::{CorporateActions, CustomQuote, StockQuote, Timeframe};
+use crate::utils::resample_utils::resample;
+
+use super::MarketDataProvider;
+
+pub const DEFAULT_BASE_URL: &str = "http://localhost:3000";
+pub const BASE_URL_ENV: &str = "MARKET_DATA_URL";
+
+/// Intervals the proxy can't serve directly, and the interval they are resampled from.
+const RESAMPLED_TIMEFRAMES: [(Timeframe, Timeframe); 1] = [(Timeframe::FourHours, Timeframe::OneHour)];
+
+/// Talks to the Node proxy in `api/server.js` (or anything exposing the same routes).
+pub struct ApiProvider {
+    base_url: String,
+    client: reqwest::Client,
+}
+
+impl ApiProvider {
+    pub fn new(base_url: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            client: reqwest::Client::new(),
+        }
+    }
+
+    pub fn from_env() -> Self {
+        let base_url = std::env::var(BASE_URL_ENV).unwrap_or_else(|_| DEFAULT_BASE_URL.to_string());
+        Self::new(&base_url)
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    async fn fetch_native_bars(
+        &self,
+        symbol: &str,
+        timeframe: Timeframe,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<StockQuote>, String> {
+        let url = format!(
+            "{}/stock/chart/{}?timeframe={}&period1={}&period2={}",
+            self.base_url,
+            symbol,
+            timeframe,
+            from.to_rfc3339_opts(SecondsFormat::Secs, true),
+            to.to_rfc3339_opts(SecondsFormat::Secs, true)
+        );
+
+        let response = self.client.get(&url).send().await.map_err(|e| e.to_string())?;
+
+        match response.status() {
+            StatusCode::OK => {
+                let stock_data: Vec<StockQuote> = response.json().await.map_err(|e| e.to_string())?;
+                Ok(stock_data)
+            }
+            status => Err(format!("Failed to fetch stock data: HTTP {}", status)),
+        }
+    }
+}
+
+#[async_trait]
+impl MarketDataProvider for ApiProvider {
+    async fn fetch_bars(
+        &self,
+        symbol: &str,
+        timeframe: Timeframe,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<StockQuote>, String> {
+        let source = RESAMPLED_TIMEFRAMES
+            .iter()
+            .find(|(target, _)| *target == timeframe)
+            .map(|(_, source)| *source);
+
+        match source {
+            Some(source) => {
+                let mut quotes: Vec<CustomQuote> = self
+                    .fetch_native_bars(symbol, source, from, to)
+                    .await?
+                    .iter()
+                    .map(CustomQuote::from)
+                    .collect();
+                quotes.sort_by_key(|quote| quote.time);
+                Ok(resample(&quotes, timeframe).iter().map(StockQuote::from).collect())
+            }
+            None => self.fetch_native_bars(symbol, timeframe, from, to).await,
+        }
+    }
+
+    async fn search_symbols(&self, query: &str) -> Result<Vec<String>, String> {
+        let url = format!("{}/search-stocks/{}", self.base_url, query);
+
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to fetch stock data: {}", e))?;
+        if !response.status().is_success() {
+            return Err(format!(
+                "Failed to fetch stock data: HTTP {}",
+                response.status()
+            ));
+        }
+        response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse response: {}", e))
+    }
+
+    async fn fetch_corporate_actions(
+        &self,
+        symbol: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<CorporateActions, String> {
+        let url = format!(
+            "{}/stock/events/{}?period1={}&period2={}",
+            self.base_url,
+            symbol,
+            from.to_rfc3339_opts(SecondsFormat::Secs, true),
+            to.to_rfc3339_opts(SecondsFormat::Secs, true)
+        );
+
+        let response = self.client.get(&url).send().await.map_err(|e| e.to_string())?;
+
+        match response.status() {
+            StatusCode::OK => response.json().await.map_err(|e| e.to_string()),
+            status => Err(format!("Failed to fetch corporate actions: HTTP {}", status)),
+        }
+    }
+
+    /// Yahoo's chart OHLC are split-adjusted (but not dividend-adjusted).
+    fn bars_split_adjusted(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base_url_trailing_slash() {
+        let provider = ApiProvider::new("http://example.com:8080/");
+        assert_eq!(provider.base_url(), "http://example.com:8080");
+    }
+}
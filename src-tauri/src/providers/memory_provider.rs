@@ -0,0 +1,124 @@
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::types::{CorporateActions, StockQuote, Timeframe};
+
+use super::MarketDataProvider;
+
+/// Serves bars from memory, for tests and offline runs.
+#[derive(Default)]
+pub struct MemoryProvider {
+    bars: RwLock<HashMap<(String, Timeframe), Vec<StockQuote>>>,
+    actions: RwLock<HashMap<String, CorporateActions>>,
+}
+
+impl MemoryProvider {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert_bars(&self, symbol: &str, timeframe: Timeframe, bars: Vec<StockQuote>) {
+        let mut map = self.bars.write().unwrap();
+        map.entry((symbol.to_string(), timeframe))
+            .or_default()
+            .extend(bars);
+    }
+
+    pub fn insert_corporate_actions(&self, symbol: &str, actions: CorporateActions) {
+        self.actions.write().unwrap().insert(symbol.to_string(), actions);
+    }
+}
+
+#[async_trait]
+impl MarketDataProvider for MemoryProvider {
+    async fn fetch_bars(
+        &self,
+        symbol: &str,
+        timeframe: Timeframe,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<StockQuote>, String> {
+        let map = self.bars.read().map_err(|_| "Failed to acquire lock on bars")?;
+        let bars = map
+            .get(&(symbol.to_string(), timeframe))
+            .ok_or_else(|| format!("No data for {} {}", symbol, timeframe))?;
+        Ok(bars
+            .iter()
+            .filter(|quote| quote.date >= from && quote.date <= to)
+            .cloned()
+            .collect())
+    }
+
+    async fn search_symbols(&self, query: &str) -> Result<Vec<String>, String> {
+        let map = self.bars.read().map_err(|_| "Failed to acquire lock on bars")?;
+        let query = query.to_uppercase();
+        let mut symbols: Vec<String> = map
+            .keys()
+            .map(|(symbol, _)| symbol.clone())
+            .filter(|symbol| symbol.to_uppercase().contains(&query))
+            .collect();
+        symbols.sort();
+        symbols.dedup();
+        Ok(symbols)
+    }
+
+    async fn fetch_corporate_actions(
+        &self,
+        symbol: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<CorporateActions, String> {
+        let map = self.actions.read().map_err(|_| "Failed to acquire lock on corporate actions")?;
+        let mut actions = map.get(symbol).cloned().unwrap_or_default();
+        actions.events.dividends.retain(|dividend| dividend.date >= from && dividend.date <= to);
+        actions.events.splits.retain(|split| split.date >= from && split.date <= to);
+        Ok(actions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+
+    fn quote(date: DateTime<Utc>) -> StockQuote {
+        StockQuote {
+            high: Some(1.0),
+            volume: Some(100),
+            open: Some(1.0),
+            low: Some(1.0),
+            close: Some(1.0),
+            date,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fetch_bars_filters_range() {
+        let provider = MemoryProvider::new();
+        let start = Utc.with_ymd_and_hms(2022, 1, 1, 0, 0, 0).unwrap();
+        let bars = (0..10).map(|i| quote(start + Duration::days(i))).collect();
+        provider.insert_bars("AAPL", Timeframe::OneDay, bars);
+
+        let result = provider
+            .fetch_bars("AAPL", Timeframe::OneDay, start + Duration::days(2), start + Duration::days(5))
+            .await
+            .unwrap();
+        assert_eq!(result.len(), 4);
+        assert!(provider.fetch_bars("MSFT", Timeframe::OneDay, start, start).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_search_symbols() {
+        let provider = MemoryProvider::new();
+        let start = Utc.with_ymd_and_hms(2022, 1, 1, 0, 0, 0).unwrap();
+        provider.insert_bars("AAPL", Timeframe::OneDay, vec![quote(start)]);
+        provider.insert_bars("AAPL", Timeframe::OneHour, vec![quote(start)]);
+        provider.insert_bars("AMZN", Timeframe::OneDay, vec![quote(start)]);
+
+        assert_eq!(provider.search_symbols("aa").await.unwrap(), vec!["AAPL"]);
+        assert_eq!(provider.search_symbols("A").await.unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,50 @@
+pub mod api_provider;
+pub mod memory_provider;
+
+use std::sync::{Arc, RwLock};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+
+use crate::types::{CorporateActions, StockQuote, Timeframe};
+use api_provider::ApiProvider;
+
+#[async_trait]
+pub trait MarketDataProvider: Send + Sync {
+    async fn fetch_bars(
+        &self,
+        symbol: &str,
+        timeframe: Timeframe,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<StockQuote>, String>;
+
+    async fn search_symbols(&self, query: &str) -> Result<Vec<String>, String>;
+
+    async fn fetch_corporate_actions(
+        &self,
+        symbol: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<CorporateActions, String>;
+
+    /// Whether `fetch_bars` returns prices already back-adjusted for splits, so split
+    /// adjustment must not be applied again. `ApiProvider` serves Yahoo chart bars, which
+    /// are split-adjusted; `MemoryProvider` returns raw prices as they were inserted. No
+    /// provider adjusts bars for dividends.
+    fn bars_split_adjusted(&self) -> bool {
+        false
+    }
+}
+
+pub static PROVIDER: Lazy<RwLock<Arc<dyn MarketDataProvider>>> =
+    Lazy::new(|| RwLock::new(Arc::new(ApiProvider::from_env())));
+
+pub fn provider() -> Arc<dyn MarketDataProvider> {
+    PROVIDER.read().unwrap().clone()
+}
+
+pub fn set_provider(provider: Arc<dyn MarketDataProvider>) {
+    *PROVIDER.write().unwrap() = provider;
+}
@@ -0,0 +1,47 @@
+use crate::types::CustomQuote;
+use crate::utils::indicator_utils::calculate_sma;
+
+use super::{param_usize, ParamSpec, Strategy, StrategyParams};
+
+/// Long while the fast, medium and slow SMAs are stacked upwards, short while stacked downwards.
+pub struct AlligatorStrategy;
+
+impl Strategy for AlligatorStrategy {
+    fn id(&self) -> &'static str {
+        "alligator"
+    }
+
+    fn name(&self) -> &'static str {
+        "Alligator (3 SMA)"
+    }
+
+    fn params(&self) -> Vec<ParamSpec> {
+        vec![
+            ParamSpec::integer("period1", "Fast SMA", 5, 1, 500),
+            ParamSpec::integer("period2", "Medium SMA", 8, 1, 500),
+            ParamSpec::integer("period3", "Slow SMA", 13, 1, 500),
+        ]
+    }
+
+    fn warmup(&self, params: &StrategyParams) -> usize {
+        param_usize(params, "period1")
+            .max(param_usize(params, "period2"))
+            .max(param_usize(params, "period3"))
+    }
+
+    fn signals(&self, data: &[CustomQuote], params: &StrategyParams) -> Vec<String> {
+        let sma1 = calculate_sma(data, param_usize(params, "period1"));
+        let sma2 = calculate_sma(data, param_usize(params, "period2"));
+        let sma3 = calculate_sma(data, param_usize(params, "period3"));
+
+        let mut signals = vec!["hold".to_string(); data.len()];
+        for i in 1..data.len() {
+            if sma1[i].value > sma2[i].value && sma2[i].value > sma3[i].value {
+                signals[i] = "buy".to_string();
+            } else if sma1[i].value < sma2[i].value && sma2[i].value < sma3[i].value {
+                signals[i] = "sell".to_string();
+            }
+        }
+        signals
+    }
+}
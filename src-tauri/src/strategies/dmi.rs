@@ -0,0 +1,48 @@
+use crate::types::CustomQuote;
+use crate::utils::indicator_utils::calculate_dmi;
+
+use super::{param_f64, param_usize, ParamSpec, Strategy, StrategyParams};
+
+/// Follows the dominant directional indicator, but only while ADX shows a trend.
+pub struct DmiStrategy;
+
+impl Strategy for DmiStrategy {
+    fn id(&self) -> &'static str {
+        "dmi"
+    }
+
+    fn name(&self) -> &'static str {
+        "ADX / DMI trend"
+    }
+
+    fn params(&self) -> Vec<ParamSpec> {
+        vec![
+            ParamSpec::integer("period", "DMI period", 14, 1, 500),
+            ParamSpec::float("adx_threshold", "Minimum ADX", 25.0, 0.0, 100.0),
+        ]
+    }
+
+    fn warmup(&self, params: &StrategyParams) -> usize {
+        2 * param_usize(params, "period")
+    }
+
+    fn signals(&self, data: &[CustomQuote], params: &StrategyParams) -> Vec<String> {
+        let dmi = calculate_dmi(data, param_usize(params, "period"));
+        let threshold = param_f64(params, "adx_threshold");
+
+        (0..data.len())
+            .map(|i| {
+                if dmi.adx[i].value < threshold {
+                    "hold"
+                } else if dmi.plus_di[i].value > dmi.minus_di[i].value {
+                    "buy"
+                } else if dmi.plus_di[i].value < dmi.minus_di[i].value {
+                    "sell"
+                } else {
+                    "hold"
+                }
+                .to_string()
+            })
+            .collect()
+    }
+}
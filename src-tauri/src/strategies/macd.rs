@@ -0,0 +1,53 @@
+use crate::types::CustomQuote;
+use crate::utils::indicator_utils::calculate_macd;
+
+use super::{param_usize, ParamSpec, Strategy, StrategyParams};
+
+/// Long while the MACD line is above its signal line, short while below.
+pub struct MacdStrategy;
+
+impl Strategy for MacdStrategy {
+    fn id(&self) -> &'static str {
+        "macd"
+    }
+
+    fn name(&self) -> &'static str {
+        "MACD crossover"
+    }
+
+    fn params(&self) -> Vec<ParamSpec> {
+        vec![
+            ParamSpec::integer("macd_short", "Short EMA", 12, 1, 500),
+            ParamSpec::integer("macd_long", "Long EMA", 26, 1, 500),
+            ParamSpec::integer("macd_signal", "Signal EMA", 9, 1, 500),
+        ]
+    }
+
+    fn warmup(&self, params: &StrategyParams) -> usize {
+        param_usize(params, "macd_short")
+            .max(param_usize(params, "macd_long"))
+            .max(param_usize(params, "macd_signal"))
+    }
+
+    fn signals(&self, data: &[CustomQuote], params: &StrategyParams) -> Vec<String> {
+        if data.is_empty() {
+            return Vec::new();
+        }
+        let (macd_line, signal_line, _) = calculate_macd(
+            data,
+            param_usize(params, "macd_short"),
+            param_usize(params, "macd_long"),
+            param_usize(params, "macd_signal"),
+        );
+
+        let mut signals = vec!["hold".to_string(); data.len()];
+        for i in 0..data.len() {
+            if macd_line[i].value > signal_line[i].value {
+                signals[i] = "buy".to_string();
+            } else if macd_line[i].value < signal_line[i].value {
+                signals[i] = "sell".to_string();
+            }
+        }
+        signals
+    }
+}
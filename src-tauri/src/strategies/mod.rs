@@ -0,0 +1,195 @@
+pub mod alligator;
+pub mod dmi;
+pub mod macd;
+pub mod optimizer;
+pub mod supertrend;
+pub mod three_ema;
+pub mod walk_forward;
+
+use std::collections::HashMap;
+
+use once_cell::sync::Lazy;
+use serde::Serialize;
+
+use crate::types::CustomQuote;
+use alligator::AlligatorStrategy;
+use dmi::DmiStrategy;
+use macd::MacdStrategy;
+use supertrend::SupertrendStrategy;
+use three_ema::ThreeEmaStrategy;
+
+pub type StrategyParams = HashMap<String, f64>;
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+pub enum ParamKind {
+    Integer,
+    Float,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct ParamSpec {
+    pub name: &'static str,
+    pub label: &'static str,
+    pub kind: ParamKind,
+    pub default: f64,
+    pub min: f64,
+    pub max: f64,
+}
+
+impl ParamSpec {
+    pub fn integer(name: &'static str, label: &'static str, default: usize, min: usize, max: usize) -> Self {
+        Self {
+            name,
+            label,
+            kind: ParamKind::Integer,
+            default: default as f64,
+            min: min as f64,
+            max: max as f64,
+        }
+    }
+
+    pub fn float(name: &'static str, label: &'static str, default: f64, min: f64, max: f64) -> Self {
+        Self {
+            name,
+            label,
+            kind: ParamKind::Float,
+            default,
+            min,
+            max,
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct StrategyInfo {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub params: Vec<ParamSpec>,
+}
+
+/// A signal generator for the backtester. `signals` returns one of "buy", "sell" or
+/// "hold" per bar; bars inside the warm-up are forced to "hold" by the caller.
+pub trait Strategy: Send + Sync {
+    fn id(&self) -> &'static str;
+
+    fn name(&self) -> &'static str;
+
+    fn params(&self) -> Vec<ParamSpec>;
+
+    fn warmup(&self, params: &StrategyParams) -> usize;
+
+    fn signals(&self, data: &[CustomQuote], params: &StrategyParams) -> Vec<String>;
+
+    fn info(&self) -> StrategyInfo {
+        StrategyInfo {
+            id: self.id(),
+            name: self.name(),
+            params: self.params(),
+        }
+    }
+
+    /// Fills in defaults and rejects unknown, out-of-range or non-integer values.
+    fn resolve_params(&self, params: &StrategyParams) -> Result<StrategyParams, String> {
+        resolve_param_specs(self.id(), &self.params(), params)
+    }
+}
+
+/// Shared parameter resolution for strategies and indicators.
+pub fn resolve_param_specs(
+    owner: &str,
+    specs: &[ParamSpec],
+    params: &StrategyParams,
+) -> Result<StrategyParams, String> {
+    if let Some(unknown) = params.keys().find(|name| !specs.iter().any(|spec| spec.name == *name)) {
+        return Err(format!("Unknown parameter {} for {}", unknown, owner));
+    }
+
+    let mut resolved = StrategyParams::new();
+    for spec in specs {
+        let value = params.get(spec.name).copied().unwrap_or(spec.default);
+        if !(spec.min..=spec.max).contains(&value) {
+            return Err(format!(
+                "{} must be between {} and {}",
+                spec.name, spec.min, spec.max
+            ));
+        }
+        if spec.kind == ParamKind::Integer && value.fract() != 0.0 {
+            return Err(format!("{} must be an integer", spec.name));
+        }
+        resolved.insert(spec.name.to_string(), value);
+    }
+    Ok(resolved)
+}
+
+pub static STRATEGIES: Lazy<Vec<Box<dyn Strategy>>> = Lazy::new(|| {
+    vec![
+        Box::new(AlligatorStrategy),
+        Box::new(MacdStrategy),
+        Box::new(ThreeEmaStrategy),
+        Box::new(SupertrendStrategy),
+        Box::new(DmiStrategy),
+    ]
+});
+
+pub fn find_strategy(id: &str) -> Result<&'static dyn Strategy, String> {
+    STRATEGIES
+        .iter()
+        .find(|strategy| strategy.id() == id)
+        .map(|strategy| strategy.as_ref())
+        .ok_or_else(|| format!("Unknown strategy: {}", id))
+}
+
+/// Reads a resolved integer parameter; resolution guarantees it is present.
+pub fn param_usize(params: &StrategyParams, name: &str) -> usize {
+    params.get(name).copied().unwrap_or(0.0) as usize
+}
+
+pub fn param_f64(params: &StrategyParams, name: &str) -> f64 {
+    params.get(name).copied().unwrap_or(0.0)
+}
+
+/// Runs the strategy with resolved parameters and masks the warm-up bars.
+pub fn generate_signals(
+    strategy: &dyn Strategy,
+    data: &[CustomQuote],
+    params: &StrategyParams,
+) -> Result<Vec<String>, String> {
+    let params = strategy.resolve_params(params)?;
+    let mut signals = strategy.signals(data, &params);
+    let warmup = strategy.warmup(&params).min(signals.len());
+    for signal in signals.iter_mut().take(warmup) {
+        *signal = "hold".to_string();
+    }
+    Ok(signals)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_ids_are_unique() {
+        let mut ids: Vec<&str> = STRATEGIES.iter().map(|strategy| strategy.id()).collect();
+        ids.sort();
+        ids.dedup();
+        assert_eq!(ids.len(), STRATEGIES.len());
+        assert!(find_strategy("macd").is_ok());
+        assert!(find_strategy("unknown").is_err());
+    }
+
+    #[test]
+    fn test_resolve_params() {
+        let strategy = find_strategy("alligator").unwrap();
+        let resolved = strategy.resolve_params(&StrategyParams::new()).unwrap();
+        assert_eq!(param_usize(&resolved, "period1"), 5);
+
+        let mut params = StrategyParams::new();
+        params.insert("period1".to_string(), 2.5);
+        assert!(strategy.resolve_params(&params).is_err());
+        params.insert("period1".to_string(), 0.0);
+        assert!(strategy.resolve_params(&params).is_err());
+        params.clear();
+        params.insert("length".to_string(), 3.0);
+        assert!(strategy.resolve_params(&params).is_err());
+    }
+}
@@ -0,0 +1,349 @@
+use std::collections::{BTreeMap, HashSet};
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::types::{CustomQuote, StrategyResult, Timeframe};
+use crate::utils::backtest_utils::{calculate_performance, BacktestConfig};
+
+use super::{generate_signals, ParamKind, Strategy, StrategyParams};
+
+/// Upper bound on backtests per optimization, so a careless grid can't hang the app.
+pub const MAX_RUNS: usize = 20_000;
+
+/// Inclusive range swept for one parameter.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ParamRange {
+    pub name: String,
+    pub start: f64,
+    pub end: f64,
+    pub step: f64,
+}
+
+impl ParamRange {
+    pub fn values(&self) -> Result<Vec<f64>, String> {
+        if self.step <= 0.0 || self.end < self.start {
+            return Err(format!("Invalid range for {}", self.name));
+        }
+        // Checked before collecting so a huge range fails instead of exhausting memory.
+        let count = ((self.end - self.start) / self.step + 1e-9).floor() + 1.0;
+        if !count.is_finite() || count > MAX_RUNS as f64 {
+            return Err(format!("Range for {} has more than {} values", self.name, MAX_RUNS));
+        }
+        let count = count as usize;
+        Ok((0..count).map(|i| self.start + i as f64 * self.step).collect())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SearchMethod {
+    /// Every combination of the range values.
+    #[default]
+    Grid,
+    /// `samples` combinations drawn uniformly from the range values.
+    Random { samples: usize, seed: Option<u64> },
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Objective {
+    #[default]
+    TotalReturn,
+    SharpeRatio,
+    ProfitFactor,
+    /// Total return, among runs whose max drawdown stays within `max_drawdown_percent`.
+    ConstrainedReturn { max_drawdown_percent: f64 },
+}
+
+impl Objective {
+    /// Higher is better; `None` marks a run that violates the objective's constraint.
+    pub fn score(&self, result: &StrategyResult) -> Option<f64> {
+        let score = match self {
+            Objective::TotalReturn => result.total_return_percentage,
+            Objective::SharpeRatio => result.metrics.sharpe_ratio,
+            Objective::ProfitFactor => result.profit_factor,
+            Objective::ConstrainedReturn { max_drawdown_percent } => {
+                if result.max_drawdown_percentage.abs() > *max_drawdown_percent {
+                    return None;
+                }
+                result.total_return_percentage
+            }
+        };
+        score.is_finite().then_some(score)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct OptimizationSpec {
+    pub ranges: Vec<ParamRange>,
+    pub method: SearchMethod,
+    pub objective: Objective,
+    /// Two parameter names to aggregate scores over for the heatmap (x, y).
+    pub heatmap: Option<(String, String)>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct OptimizationRun {
+    pub params: StrategyParams,
+    pub score: Option<f64>,
+    pub total_return_percentage: f64,
+    pub sharpe_ratio: f64,
+    pub profit_factor: f64,
+    pub max_drawdown_percentage: f64,
+    pub num_trades: usize,
+}
+
+/// Best score for each (x, y) cell over all values of the other parameters;
+/// `scores[y][x]` is `None` where no run was feasible.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct Heatmap {
+    pub x_param: String,
+    pub y_param: String,
+    pub x_values: Vec<f64>,
+    pub y_values: Vec<f64>,
+    pub scores: Vec<Vec<Option<f64>>>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct OptimizationResult {
+    /// All runs, best first; infeasible runs last.
+    pub runs: Vec<OptimizationRun>,
+    pub heatmap: Option<Heatmap>,
+}
+
+/// Parameter sets to evaluate. Integer parameters are rounded and duplicates dropped.
+pub fn candidates(strategy: &dyn Strategy, spec: &OptimizationSpec) -> Result<Vec<StrategyParams>, String> {
+    let specs = strategy.params();
+    let mut axes: Vec<(String, Vec<f64>)> = Vec::new();
+    for range in &spec.ranges {
+        let param = specs
+            .iter()
+            .find(|param| param.name == range.name)
+            .ok_or_else(|| format!("Unknown parameter {} for {}", range.name, strategy.id()))?;
+        let mut values = range.values()?;
+        if param.kind == ParamKind::Integer {
+            values.iter_mut().for_each(|value| *value = value.round());
+            values.dedup();
+        }
+        axes.push((range.name.clone(), values));
+    }
+
+    let grid_size = axes.iter().try_fold(1usize, |size, (_, values)| size.checked_mul(values.len()));
+    let combinations: Vec<StrategyParams> = match spec.method {
+        SearchMethod::Grid => {
+            if grid_size.is_none_or(|size| size > MAX_RUNS) {
+                return Err(format!("Grid has more than {} combinations", MAX_RUNS));
+            }
+            axes.iter().fold(vec![StrategyParams::new()], |combinations, (name, values)| {
+                combinations
+                    .iter()
+                    .flat_map(|params| {
+                        values.iter().map(move |value| {
+                            let mut params = params.clone();
+                            params.insert(name.clone(), *value);
+                            params
+                        })
+                    })
+                    .collect()
+            })
+        }
+        SearchMethod::Random { samples, seed } => {
+            if samples > MAX_RUNS {
+                return Err(format!("Random search is limited to {} samples", MAX_RUNS));
+            }
+            let mut rng = match seed {
+                Some(seed) => StdRng::seed_from_u64(seed),
+                None => StdRng::from_entropy(),
+            };
+            (0..samples)
+                .map(|_| {
+                    axes.iter()
+                        .map(|(name, values)| (name.clone(), values[rng.gen_range(0..values.len())]))
+                        .collect()
+                })
+                .collect()
+        }
+    };
+
+    let mut seen: HashSet<Vec<u64>> = HashSet::new();
+    Ok(combinations
+        .into_iter()
+        .filter(|params| seen.insert(axes.iter().map(|(name, _)| params[name].to_bits()).collect()))
+        .collect())
+}
+
+/// Backtests every candidate on `data` in parallel and ranks them by `spec.objective`.
+/// Candidates the strategy rejects (e.g. out of bounds) are skipped.
+pub fn optimize(
+    strategy: &dyn Strategy,
+    data: &[CustomQuote],
+    timeframe: Timeframe,
+    config: &BacktestConfig,
+    spec: &OptimizationSpec,
+) -> Result<OptimizationResult, String> {
+    let candidates = candidates(strategy, spec)?;
+
+    let mut runs: Vec<OptimizationRun> = candidates
+        .par_iter()
+        .filter_map(|params| {
+            let signals = generate_signals(strategy, data, params).ok()?;
+            let result = calculate_performance(data, &signals, timeframe, config);
+            Some(OptimizationRun {
+                params: params.clone(),
+                score: spec.objective.score(&result),
+                total_return_percentage: result.total_return_percentage,
+                sharpe_ratio: result.metrics.sharpe_ratio,
+                profit_factor: result.profit_factor,
+                max_drawdown_percentage: result.max_drawdown_percentage,
+                num_trades: result.num_trades,
+            })
+        })
+        .collect();
+    if runs.is_empty() {
+        return Err("No valid parameter combinations".to_string());
+    }
+
+    runs.sort_by(|a, b| match (a.score, b.score) {
+        (Some(a), Some(b)) => b.total_cmp(&a),
+        (a, b) => b.is_some().cmp(&a.is_some()),
+    });
+
+    let heatmap = spec
+        .heatmap
+        .as_ref()
+        .map(|(x_param, y_param)| heatmap(&runs, x_param, y_param));
+    Ok(OptimizationResult { runs, heatmap })
+}
+
+fn heatmap(runs: &[OptimizationRun], x_param: &str, y_param: &str) -> Heatmap {
+    // f64 isn't Ord; key cells by the value's bit pattern (values come from the same ranges).
+    let mut cells: BTreeMap<(u64, u64), Option<f64>> = BTreeMap::new();
+    let mut x_values: Vec<f64> = Vec::new();
+    let mut y_values: Vec<f64> = Vec::new();
+
+    for run in runs {
+        let (Some(&x), Some(&y)) = (run.params.get(x_param), run.params.get(y_param)) else {
+            continue;
+        };
+        if !x_values.contains(&x) {
+            x_values.push(x);
+        }
+        if !y_values.contains(&y) {
+            y_values.push(y);
+        }
+        let cell = cells.entry((x.to_bits(), y.to_bits())).or_insert(None);
+        if run.score > *cell {
+            *cell = run.score;
+        }
+    }
+    x_values.sort_by(f64::total_cmp);
+    y_values.sort_by(f64::total_cmp);
+
+    let scores = y_values
+        .iter()
+        .map(|y| {
+            x_values
+                .iter()
+                .map(|x| cells.get(&(x.to_bits(), y.to_bits())).copied().flatten())
+                .collect()
+        })
+        .collect();
+
+    Heatmap {
+        x_param: x_param.to_string(),
+        y_param: y_param.to_string(),
+        x_values,
+        y_values,
+        scores,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategies::find_strategy;
+    use chrono::{Duration, TimeZone, Utc};
+
+    fn range(name: &str, start: f64, end: f64, step: f64) -> ParamRange {
+        ParamRange {
+            name: name.to_string(),
+            start,
+            end,
+            step,
+        }
+    }
+
+    fn quotes() -> Vec<CustomQuote> {
+        let start = Utc.with_ymd_and_hms(2022, 1, 3, 0, 0, 0).unwrap();
+        (0..120)
+            .map(|day| {
+                let close = 100.0 + 10.0 * (day as f64 / 9.0).sin() + day as f64 * 0.1;
+                CustomQuote {
+                    high: Some(close + 1.0),
+                    volume: Some(1000),
+                    open: Some(close),
+                    low: Some(close - 1.0),
+                    close: Some(close),
+                    time: start + Duration::days(day),
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_grid_candidates() {
+        let strategy = find_strategy("three_ema").unwrap();
+        let spec = OptimizationSpec {
+            ranges: vec![range("period1", 2.0, 6.0, 2.0), range("period3", 20.0, 30.0, 5.0)],
+            ..Default::default()
+        };
+        assert_eq!(candidates(strategy, &spec).unwrap().len(), 9);
+
+        let random = OptimizationSpec {
+            method: SearchMethod::Random { samples: 5, seed: Some(7) },
+            ..spec.clone()
+        };
+        assert!(candidates(strategy, &random).unwrap().len() <= 5);
+
+        let unknown = OptimizationSpec {
+            ranges: vec![range("length", 1.0, 2.0, 1.0)],
+            ..Default::default()
+        };
+        assert!(candidates(strategy, &unknown).is_err());
+    }
+
+    #[test]
+    fn test_huge_range_rejected_before_allocating() {
+        assert!(range("period1", 0.0, 1e12, 1.0).values().is_err());
+        assert!(range("period1", 0.0, f64::INFINITY, 1.0).values().is_err());
+        assert_eq!(range("period1", 1.0, MAX_RUNS as f64, 1.0).values().unwrap().len(), MAX_RUNS);
+    }
+
+    #[test]
+    fn test_optimize_ranks_runs_and_builds_heatmap() {
+        let strategy = find_strategy("three_ema").unwrap();
+        let spec = OptimizationSpec {
+            ranges: vec![
+                range("period1", 2.0, 4.0, 1.0),
+                range("period2", 6.0, 8.0, 2.0),
+                range("period3", 15.0, 25.0, 10.0),
+            ],
+            heatmap: Some(("period1".to_string(), "period2".to_string())),
+            ..Default::default()
+        };
+        let result = optimize(strategy, &quotes(), Timeframe::OneDay, &BacktestConfig::default(), &spec).unwrap();
+
+        assert_eq!(result.runs.len(), 12);
+        assert!(result.runs.windows(2).all(|pair| pair[0].score >= pair[1].score));
+        let heatmap = result.heatmap.unwrap();
+        assert_eq!(heatmap.x_values, vec![2.0, 3.0, 4.0]);
+        assert_eq!(heatmap.y_values, vec![6.0, 8.0]);
+        assert_eq!(heatmap.scores.len(), 2);
+        let best = heatmap.scores.iter().flatten().flatten().copied().fold(f64::MIN, f64::max);
+        assert_eq!(Some(best), result.runs[0].score);
+    }
+}
@@ -0,0 +1,43 @@
+use crate::types::CustomQuote;
+use crate::utils::indicator_utils::calculate_supertrend;
+
+use super::{param_f64, param_usize, ParamSpec, Strategy, StrategyParams};
+
+/// Long while the close is above the Supertrend line, short while below.
+pub struct SupertrendStrategy;
+
+impl Strategy for SupertrendStrategy {
+    fn id(&self) -> &'static str {
+        "supertrend"
+    }
+
+    fn name(&self) -> &'static str {
+        "Supertrend"
+    }
+
+    fn params(&self) -> Vec<ParamSpec> {
+        vec![
+            ParamSpec::integer("period", "ATR period", 10, 1, 500),
+            ParamSpec::float("multiplier", "ATR multiple", 3.0, 0.1, 10.0),
+        ]
+    }
+
+    fn warmup(&self, params: &StrategyParams) -> usize {
+        param_usize(params, "period")
+    }
+
+    fn signals(&self, data: &[CustomQuote], params: &StrategyParams) -> Vec<String> {
+        let supertrend = calculate_supertrend(
+            data,
+            param_usize(params, "period"),
+            param_f64(params, "multiplier"),
+        );
+
+        data.iter()
+            .zip(&supertrend)
+            .map(|(quote, line)| {
+                if quote.close.unwrap_or(0.0) > line.value { "buy" } else { "sell" }.to_string()
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,50 @@
+use crate::types::CustomQuote;
+use crate::utils::indicator_utils::calculate_ema;
+
+use super::{param_usize, ParamSpec, Strategy, StrategyParams};
+
+/// Long while the fast, medium and slow EMAs are stacked upwards, short while stacked downwards.
+pub struct ThreeEmaStrategy;
+
+impl Strategy for ThreeEmaStrategy {
+    fn id(&self) -> &'static str {
+        "three_ema"
+    }
+
+    fn name(&self) -> &'static str {
+        "Three EMA"
+    }
+
+    fn params(&self) -> Vec<ParamSpec> {
+        vec![
+            ParamSpec::integer("period1", "Fast EMA", 5, 1, 500),
+            ParamSpec::integer("period2", "Medium EMA", 10, 1, 500),
+            ParamSpec::integer("period3", "Slow EMA", 20, 1, 500),
+        ]
+    }
+
+    fn warmup(&self, params: &StrategyParams) -> usize {
+        param_usize(params, "period1")
+            .max(param_usize(params, "period2"))
+            .max(param_usize(params, "period3"))
+    }
+
+    fn signals(&self, data: &[CustomQuote], params: &StrategyParams) -> Vec<String> {
+        if data.is_empty() {
+            return Vec::new();
+        }
+        let ema1 = calculate_ema(data, param_usize(params, "period1"));
+        let ema2 = calculate_ema(data, param_usize(params, "period2"));
+        let ema3 = calculate_ema(data, param_usize(params, "period3"));
+
+        let mut signals = vec!["hold".to_string(); data.len()];
+        for i in 1..data.len() {
+            if ema1[i].value > ema2[i].value && ema2[i].value > ema3[i].value {
+                signals[i] = "buy".to_string();
+            } else if ema1[i].value < ema2[i].value && ema2[i].value < ema3[i].value {
+                signals[i] = "sell".to_string();
+            }
+        }
+        signals
+    }
+}
@@ -0,0 +1,228 @@
+use serde::{Deserialize, Serialize};
+
+use crate::types::{CustomQuote, Timeframe};
+use crate::utils::backtest_utils::{calculate_performance, BacktestConfig};
+
+use super::optimizer::{optimize, OptimizationSpec};
+use super::{generate_signals, Strategy, StrategyParams};
+
+/// Window lengths are in bars. Rolling windows slide the in-sample window forward by the
+/// out-of-sample length; anchored windows keep the in-sample start at the first bar.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct WalkForwardSpec {
+    pub optimization: OptimizationSpec,
+    pub in_sample_bars: usize,
+    pub out_of_sample_bars: usize,
+    pub anchored: bool,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct WalkForwardWindow {
+    pub in_sample_start: String,
+    pub in_sample_end: String,
+    pub out_of_sample_start: String,
+    pub out_of_sample_end: String,
+    pub params: StrategyParams,
+    pub in_sample_return_percentage: f64,
+    pub out_of_sample_return_percentage: f64,
+    /// Annualized out-of-sample return over annualized in-sample return.
+    pub efficiency: Option<f64>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct WalkForwardResult {
+    pub windows: Vec<WalkForwardWindow>,
+    /// Out-of-sample equity of all windows chained together, each starting from the
+    /// previous window's final equity.
+    pub dates: Vec<String>,
+    pub equity_curve: Vec<f64>,
+    pub total_return_percentage: f64,
+    pub num_trades: usize,
+    /// Mean annualized out-of-sample return over mean annualized in-sample return.
+    pub efficiency: Option<f64>,
+}
+
+/// `(in-sample start, out-of-sample start, out-of-sample end)` bar indices of each window.
+/// The last out-of-sample window may be shorter than requested.
+pub fn windows(len: usize, spec: &WalkForwardSpec) -> Result<Vec<(usize, usize, usize)>, String> {
+    if spec.in_sample_bars == 0 || spec.out_of_sample_bars == 0 {
+        return Err("In-sample and out-of-sample windows must be at least one bar".to_string());
+    }
+    let mut windows = Vec::new();
+    let mut start = 0;
+    while start + spec.in_sample_bars < len {
+        let split = start + spec.in_sample_bars;
+        let end = (split + spec.out_of_sample_bars).min(len);
+        windows.push((if spec.anchored { 0 } else { start }, split, end));
+        start += spec.out_of_sample_bars;
+    }
+    if windows.is_empty() {
+        return Err("Not enough bars for one in-sample and out-of-sample window".to_string());
+    }
+    Ok(windows)
+}
+
+fn annualized(return_percentage: f64, bars: usize, timeframe: Timeframe) -> f64 {
+    let growth = 1.0 + return_percentage / 100.0;
+    if growth <= 0.0 || bars == 0 {
+        return -100.0;
+    }
+    (growth.powf(timeframe.periods_per_year() / bars as f64) - 1.0) * 100.0
+}
+
+fn efficiency(out_of_sample: f64, in_sample: f64) -> Option<f64> {
+    (in_sample > 0.0).then(|| out_of_sample / in_sample)
+}
+
+/// Optimizes on each in-sample window and trades the best parameters on the following
+/// out-of-sample window. Signals for an out-of-sample window are generated from the start
+/// of its in-sample window, so indicators are already warmed up when it begins.
+/// A window whose in-sample runs all violate the objective's constraint is skipped: it is
+/// left out of `windows` and the stitched equity, rather than trading infeasible parameters.
+/// Errors if every window is skipped.
+pub fn walk_forward(
+    strategy: &dyn Strategy,
+    data: &[CustomQuote],
+    timeframe: Timeframe,
+    config: &BacktestConfig,
+    spec: &WalkForwardSpec,
+) -> Result<WalkForwardResult, String> {
+    let mut result = WalkForwardResult {
+        windows: Vec::new(),
+        dates: Vec::new(),
+        equity_curve: Vec::new(),
+        total_return_percentage: 0.0,
+        num_trades: 0,
+        efficiency: None,
+    };
+    let mut in_sample_annualized = Vec::new();
+    let mut out_of_sample_annualized = Vec::new();
+
+    for (start, split, end) in windows(data.len(), spec)? {
+        let optimization = optimize(strategy, &data[start..split], timeframe, config, &spec.optimization)?;
+        let best = match optimization.runs.first() {
+            Some(best) if best.score.is_some() => best,
+            _ => continue,
+        };
+
+        let signals = generate_signals(strategy, &data[start..end], &best.params)?;
+        let out_of_sample = calculate_performance(&data[split..end], &signals[split - start..], timeframe, config);
+
+        let scale = match (result.equity_curve.last(), out_of_sample.equity_curve.first()) {
+            (Some(last), Some(first)) if *first > 0.0 => last / first,
+            _ => 1.0,
+        };
+        result.equity_curve.extend(out_of_sample.equity_curve.iter().map(|equity| equity * scale));
+        result.dates.extend(out_of_sample.dates.iter().cloned());
+        result.num_trades += out_of_sample.num_trades;
+
+        let is_annualized = annualized(best.total_return_percentage, split - start, timeframe);
+        let oos_annualized = annualized(out_of_sample.total_return_percentage, end - split, timeframe);
+        in_sample_annualized.push(is_annualized);
+        out_of_sample_annualized.push(oos_annualized);
+
+        result.windows.push(WalkForwardWindow {
+            in_sample_start: data[start].time.to_string(),
+            in_sample_end: data[split - 1].time.to_string(),
+            out_of_sample_start: data[split].time.to_string(),
+            out_of_sample_end: data[end - 1].time.to_string(),
+            params: best.params.clone(),
+            in_sample_return_percentage: best.total_return_percentage,
+            out_of_sample_return_percentage: out_of_sample.total_return_percentage,
+            efficiency: efficiency(oos_annualized, is_annualized),
+        });
+    }
+
+    if result.windows.is_empty() {
+        return Err("No in-sample window has parameters that satisfy the objective".to_string());
+    }
+    if let (Some(first), Some(last)) = (result.equity_curve.first(), result.equity_curve.last()) {
+        result.total_return_percentage = (last / first - 1.0) * 100.0;
+    }
+    let mean = |values: &[f64]| values.iter().sum::<f64>() / values.len() as f64;
+    result.efficiency = efficiency(mean(&out_of_sample_annualized), mean(&in_sample_annualized));
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategies::find_strategy;
+    use crate::strategies::optimizer::{Objective, ParamRange};
+    use chrono::{Duration, TimeZone, Utc};
+
+    #[test]
+    fn test_rolling_and_anchored_windows() {
+        let mut spec = WalkForwardSpec {
+            in_sample_bars: 10,
+            out_of_sample_bars: 5,
+            ..Default::default()
+        };
+        assert_eq!(windows(27, &spec).unwrap(), vec![(0, 10, 15), (5, 15, 20), (10, 20, 25), (15, 25, 27)]);
+        spec.anchored = true;
+        assert_eq!(windows(20, &spec).unwrap(), vec![(0, 10, 15), (0, 15, 20)]);
+        assert!(windows(10, &spec).is_err());
+    }
+
+    fn sine_quotes() -> Vec<CustomQuote> {
+        let start = Utc.with_ymd_and_hms(2022, 1, 3, 0, 0, 0).unwrap();
+        (0..200)
+            .map(|day| {
+                let close = 100.0 + 10.0 * (day as f64 / 7.0).sin();
+                CustomQuote {
+                    high: Some(close + 1.0),
+                    volume: Some(1000),
+                    open: Some(close),
+                    low: Some(close - 1.0),
+                    close: Some(close),
+                    time: start + Duration::days(day),
+                }
+            })
+            .collect()
+    }
+
+    fn spec(objective: Objective) -> WalkForwardSpec {
+        WalkForwardSpec {
+            optimization: OptimizationSpec {
+                ranges: vec![ParamRange {
+                    name: "period1".to_string(),
+                    start: 2.0,
+                    end: 4.0,
+                    step: 1.0,
+                }],
+                objective,
+                ..Default::default()
+            },
+            in_sample_bars: 80,
+            out_of_sample_bars: 40,
+            anchored: false,
+        }
+    }
+
+    #[test]
+    fn test_walk_forward_stitches_out_of_sample_equity() {
+        let data = sine_quotes();
+        let spec = spec(Objective::TotalReturn);
+        let strategy = find_strategy("three_ema").unwrap();
+        let result = walk_forward(strategy, &data, Timeframe::OneDay, &BacktestConfig::default(), &spec).unwrap();
+
+        assert_eq!(result.windows.len(), 3);
+        assert_eq!(result.equity_curve.len(), 120);
+        assert_eq!(result.dates.len(), 120);
+        assert_eq!(result.dates[0], data[80].time.to_string());
+        let final_equity = result.equity_curve.last().unwrap();
+        let expected = result
+            .windows
+            .iter()
+            .fold(result.equity_curve[0], |equity, window| equity * (1.0 + window.out_of_sample_return_percentage / 100.0));
+        assert!((final_equity - expected).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_windows_without_feasible_parameters_are_skipped() {
+        let spec = spec(Objective::ConstrainedReturn { max_drawdown_percent: 0.0 });
+        let strategy = find_strategy("three_ema").unwrap();
+        assert!(walk_forward(strategy, &sine_quotes(), Timeframe::OneDay, &BacktestConfig::default(), &spec).is_err());
+    }
+}
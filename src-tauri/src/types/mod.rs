@@ -1,8 +1,14 @@
+#[cfg(test)]
+pub mod test_support;
+pub mod timeframe;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+pub use timeframe::Timeframe;
+
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct StockQuote {
     pub high: Option<f64>,
     pub volume: Option<u64>,
@@ -22,6 +28,32 @@ pub struct CustomQuote {
     pub time: DateTime<Utc>,
 }
 
+impl From<&StockQuote> for CustomQuote {
+    fn from(quote: &StockQuote) -> Self {
+        Self {
+            high: quote.high,
+            volume: quote.volume,
+            open: quote.open,
+            low: quote.low,
+            close: quote.close,
+            time: quote.date,
+        }
+    }
+}
+
+impl From<&CustomQuote> for StockQuote {
+    fn from(quote: &CustomQuote) -> Self {
+        Self {
+            high: quote.high,
+            volume: quote.volume,
+            open: quote.open,
+            low: quote.low,
+            close: quote.close,
+            date: quote.time,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct StrategyResult {
     pub signals: Vec<String>,
@@ -33,6 +65,95 @@ pub struct StrategyResult {
     pub profit_factor: f64,
     pub final_capital: f64,
     pub total_return_percentage: f64,
+    /// Mark-to-market equity at each bar, aligned with `dates`.
+    pub equity_curve: Vec<f64>,
+    /// Percentage below the running equity peak at each bar.
+    pub drawdown: Vec<f64>,
+    pub max_drawdown_percentage: f64,
+    pub max_drawdown_peak: Option<String>,
+    pub max_drawdown_trough: Option<String>,
+    pub max_drawdown_recovery: Option<String>,
+    pub max_bars_under_water: usize,
+    /// Costs summed over all trades, in account currency; `final_capital` is net of both.
+    pub total_commission: f64,
+    pub total_slippage: f64,
+    pub total_costs: f64,
+    pub trades: Vec<Trade>,
+    pub metrics: PerformanceMetrics,
+    /// Holding the backtested symbol over the same bars, without costs.
+    pub buy_and_hold: BenchmarkComparison,
+    pub benchmark: Option<BenchmarkComparison>,
+}
+
+/// A strategy measured against holding `symbol`. Returns, alpha and tracking error are
+/// percentages; `relative_curve` is strategy growth over benchmark growth at each bar.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct BenchmarkComparison {
+    pub symbol: String,
+    pub total_return_percentage: f64,
+    pub excess_return_percentage: f64,
+    pub alpha: f64,
+    pub beta: f64,
+    pub information_ratio: f64,
+    pub tracking_error: f64,
+    pub relative_curve: Vec<f64>,
+}
+
+/// Risk-adjusted statistics of a backtest. Returns, volatility and drawdown-based figures
+/// are percentages; expectancy and average win/loss are in account currency per trade.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct PerformanceMetrics {
+    pub annualized_return: f64,
+    pub cagr: f64,
+    pub volatility: f64,
+    pub sharpe_ratio: f64,
+    pub sortino_ratio: f64,
+    pub calmar_ratio: f64,
+    pub expectancy: f64,
+    pub average_win: f64,
+    pub average_loss: f64,
+    pub longest_win_streak: usize,
+    pub longest_loss_streak: usize,
+    pub exposure_percentage: f64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TradeSide {
+    Long,
+    Short,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ExitReason {
+    Signal,
+    StopLoss,
+    TrailingStop,
+    TakeProfit,
+    MaxBars,
+    EndOfData,
+}
+
+/// One round trip from the backtester. Prices are fills after slippage; costs, P&L, MAE
+/// and MFE are in account currency. MAE/MFE are the worst and best unrealized P&L while
+/// the trade was open.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Trade {
+    pub side: TradeSide,
+    pub entry_time: String,
+    pub entry_price: f64,
+    pub exit_time: String,
+    pub exit_price: f64,
+    pub exit_reason: ExitReason,
+    pub quantity: f64,
+    pub commission: f64,
+    pub slippage: f64,
+    pub gross_pnl: f64,
+    pub net_pnl: f64,
+    pub bars_held: usize,
+    pub mae: f64,
+    pub mfe: f64,
 }
 
 #[derive(Debug, Serialize)]
@@ -46,8 +167,43 @@ pub struct IndicatorData {
     pub time: DateTime<Utc>,
     pub value: f64,
 }
-#[derive(Serialize, Deserialize, Debug)]
-pub struct EventsData;
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Dividend {
+    pub date: DateTime<Utc>,
+    pub amount: f64,
+}
 
-#[derive(Serialize, Deserialize, Debug)]
-pub struct MetaData;
+/// A `numerator`:`denominator` split, e.g. 4:1 turns one share into four.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Split {
+    pub date: DateTime<Utc>,
+    pub numerator: f64,
+    pub denominator: f64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct EventsData {
+    #[serde(default)]
+    pub dividends: Vec<Dividend>,
+    #[serde(default)]
+    pub splits: Vec<Split>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default, rename_all = "camelCase")]
+pub struct MetaData {
+    pub symbol: String,
+    pub currency: Option<String>,
+    pub exchange_name: Option<String>,
+    pub instrument_type: Option<String>,
+    pub exchange_timezone_name: Option<String>,
+    pub first_trade_date: Option<DateTime<Utc>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct CorporateActions {
+    #[serde(default)]
+    pub meta: MetaData,
+    #[serde(default)]
+    pub events: EventsData,
+}
@@ -0,0 +1,35 @@
+use chrono::Utc;
+
+use super::{CustomQuote, ExitReason, Trade, TradeSide};
+
+/// A bar with the given prices, stamped now.
+pub fn quote(open: f64, high: f64, low: f64, close: f64) -> CustomQuote {
+    CustomQuote {
+        high: Some(high),
+        volume: Some(100),
+        open: Some(open),
+        low: Some(low),
+        close: Some(close),
+        time: Utc::now(),
+    }
+}
+
+/// A one-bar long trade that realised `net_pnl` with no costs.
+pub fn trade(net_pnl: f64) -> Trade {
+    Trade {
+        side: TradeSide::Long,
+        entry_time: String::new(),
+        entry_price: 10.0,
+        exit_time: String::new(),
+        exit_price: 10.0,
+        exit_reason: ExitReason::Signal,
+        quantity: 1.0,
+        commission: 0.0,
+        slippage: 0.0,
+        gross_pnl: net_pnl,
+        net_pnl,
+        bars_held: 1,
+        mae: 0.0,
+        mfe: 0.0,
+    }
+}
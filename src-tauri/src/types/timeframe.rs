@@ -0,0 +1,207 @@
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, Datelike, Duration, Months, Utc, Weekday};
+use serde::{Deserialize, Serialize};
+
+/// Bar interval. Serialized with the same codes the frontend and the proxy use ("1M" is one minute).
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Timeframe {
+    #[serde(rename = "1M")]
+    OneMinute,
+    #[serde(rename = "5M")]
+    FiveMinutes,
+    #[serde(rename = "15M")]
+    FifteenMinutes,
+    #[serde(rename = "30M")]
+    ThirtyMinutes,
+    #[serde(rename = "1H")]
+    OneHour,
+    #[serde(rename = "4H")]
+    FourHours,
+    #[serde(rename = "1D")]
+    OneDay,
+    #[serde(rename = "1WK")]
+    OneWeek,
+    #[serde(rename = "1MO")]
+    OneMonth,
+}
+
+impl Timeframe {
+    pub const ALL: [Timeframe; 9] = [
+        Timeframe::OneMinute,
+        Timeframe::FiveMinutes,
+        Timeframe::FifteenMinutes,
+        Timeframe::ThirtyMinutes,
+        Timeframe::OneHour,
+        Timeframe::FourHours,
+        Timeframe::OneDay,
+        Timeframe::OneWeek,
+        Timeframe::OneMonth,
+    ];
+
+    /// Timeframes loaded for every symbol at startup; the rest are created on first use.
+    pub const DEFAULTS: [Timeframe; 4] = [
+        Timeframe::OneMinute,
+        Timeframe::OneHour,
+        Timeframe::OneDay,
+        Timeframe::OneWeek,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Timeframe::OneMinute => "1M",
+            Timeframe::FiveMinutes => "5M",
+            Timeframe::FifteenMinutes => "15M",
+            Timeframe::ThirtyMinutes => "30M",
+            Timeframe::OneHour => "1H",
+            Timeframe::FourHours => "4H",
+            Timeframe::OneDay => "1D",
+            Timeframe::OneWeek => "1WK",
+            Timeframe::OneMonth => "1MO",
+        }
+    }
+
+    /// Nominal length of one bar; a month counts as 30 days.
+    pub fn bar_duration(&self) -> Duration {
+        match self {
+            Timeframe::OneMinute => Duration::minutes(1),
+            Timeframe::FiveMinutes => Duration::minutes(5),
+            Timeframe::FifteenMinutes => Duration::minutes(15),
+            Timeframe::ThirtyMinutes => Duration::minutes(30),
+            Timeframe::OneHour => Duration::hours(1),
+            Timeframe::FourHours => Duration::hours(4),
+            Timeframe::OneDay => Duration::days(1),
+            Timeframe::OneWeek => Duration::weeks(1),
+            Timeframe::OneMonth => Duration::days(30),
+        }
+    }
+
+    /// How far back the first fetch for a symbol reaches.
+    pub fn initial_lookback(&self) -> Duration {
+        match self {
+            Timeframe::OneMinute => Duration::days(3),
+            Timeframe::FiveMinutes => Duration::days(5),
+            Timeframe::FifteenMinutes => Duration::days(10),
+            Timeframe::ThirtyMinutes => Duration::days(15),
+            Timeframe::OneHour => Duration::weeks(15),
+            Timeframe::FourHours => Duration::days(365),
+            Timeframe::OneDay => Duration::days(4 * 365),
+            Timeframe::OneWeek => Duration::days(7 * 365),
+            Timeframe::OneMonth => Duration::days(15 * 365),
+        }
+    }
+
+    /// How much older history each scroll-back request in `fetch_stock_chart` asks for.
+    pub fn paging_step(&self) -> Duration {
+        match self {
+            Timeframe::OneMinute => Duration::days(5),
+            Timeframe::FiveMinutes => Duration::days(10),
+            Timeframe::FifteenMinutes => Duration::days(20),
+            Timeframe::ThirtyMinutes => Duration::days(30),
+            Timeframe::OneHour => Duration::weeks(30),
+            Timeframe::FourHours => Duration::days(2 * 365),
+            Timeframe::OneDay => Duration::days(8 * 365),
+            Timeframe::OneWeek => Duration::days(15 * 365),
+            Timeframe::OneMonth => Duration::days(25 * 365),
+        }
+    }
+
+    /// Bars in a trading year (252 sessions of 6.5 hours), used to annualize backtest statistics.
+    /// A partial last bar of the session counts as a full bar.
+    pub fn periods_per_year(&self) -> f64 {
+        match self {
+            Timeframe::OneMinute => 252.0 * 390.0,
+            Timeframe::FiveMinutes => 252.0 * 78.0,
+            Timeframe::FifteenMinutes => 252.0 * 26.0,
+            Timeframe::ThirtyMinutes => 252.0 * 13.0,
+            Timeframe::OneHour => 252.0 * 7.0,
+            Timeframe::FourHours => 252.0 * 2.0,
+            Timeframe::OneDay => 252.0,
+            Timeframe::OneWeek => 52.0,
+            Timeframe::OneMonth => 12.0,
+        }
+    }
+
+    pub fn is_intraday(&self) -> bool {
+        self.bar_duration() < Duration::days(1)
+    }
+
+    /// Start of the bar after the one starting at `time`. Daily bars skip weekends and monthly
+    /// bars step by calendar month; intraday bars step by their duration across session gaps.
+    pub fn next_bar_time(&self, time: DateTime<Utc>) -> DateTime<Utc> {
+        match self {
+            Timeframe::OneDay => {
+                let mut next = time + Duration::days(1);
+                while matches!(next.weekday(), Weekday::Sat | Weekday::Sun) {
+                    next += Duration::days(1);
+                }
+                next
+            }
+            Timeframe::OneMonth => time
+                .checked_add_months(Months::new(1))
+                .unwrap_or(time + self.bar_duration()),
+            _ => time + self.bar_duration(),
+        }
+    }
+}
+
+impl fmt::Display for Timeframe {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Timeframe {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Timeframe::ALL
+            .into_iter()
+            .find(|timeframe| timeframe.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| format!("Invalid timeframe: {}", s))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip_codes() {
+        for timeframe in Timeframe::ALL {
+            assert_eq!(timeframe.as_str().parse::<Timeframe>(), Ok(timeframe));
+            let json = serde_json::to_string(&timeframe).unwrap();
+            assert_eq!(json, format!("\"{}\"", timeframe));
+            assert_eq!(serde_json::from_str::<Timeframe>(&json).unwrap(), timeframe);
+        }
+        assert_eq!("1wk".parse::<Timeframe>(), Ok(Timeframe::OneWeek));
+        assert!("2D".parse::<Timeframe>().is_err());
+    }
+
+    #[test]
+    fn test_durations_are_ordered() {
+        for pair in Timeframe::ALL.windows(2) {
+            assert!(pair[0].bar_duration() < pair[1].bar_duration());
+            assert!(pair[0].initial_lookback() < pair[0].paging_step());
+        }
+        assert!(Timeframe::FourHours.is_intraday());
+        assert!(!Timeframe::OneDay.is_intraday());
+    }
+
+    #[test]
+    fn test_next_bar_time() {
+        use chrono::TimeZone;
+
+        let friday = Utc.with_ymd_and_hms(2024, 1, 5, 0, 0, 0).unwrap();
+        assert_eq!(
+            Timeframe::OneDay.next_bar_time(friday),
+            Utc.with_ymd_and_hms(2024, 1, 8, 0, 0, 0).unwrap()
+        );
+        assert_eq!(
+            Timeframe::OneMonth.next_bar_time(Utc.with_ymd_and_hms(2024, 1, 31, 0, 0, 0).unwrap()),
+            Utc.with_ymd_and_hms(2024, 2, 29, 0, 0, 0).unwrap()
+        );
+        assert_eq!(Timeframe::FiveMinutes.next_bar_time(friday), friday + Duration::minutes(5));
+    }
+}
@@ -0,0 +1,209 @@
+use chrono::Duration;
+use serde::{Deserialize, Serialize};
+
+use crate::providers::{provider, MarketDataProvider};
+use crate::types::{CustomQuote, Dividend, EventsData, Split};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Adjustment {
+    #[default]
+    None,
+    Splits,
+    SplitsAndDividends,
+}
+
+/// Back-adjusts prices before each split by `denominator / numerator` (volume by the
+/// inverse), so a 4:1 split no longer shows up as a 75% drop.
+pub fn adjust_for_splits(quotes: &[CustomQuote], splits: &[Split]) -> Vec<CustomQuote> {
+    quotes
+        .iter()
+        .map(|quote| {
+            let factor: f64 = splits
+                .iter()
+                .filter(|split| quote.time < split.date && split.numerator > 0.0 && split.denominator > 0.0)
+                .map(|split| split.denominator / split.numerator)
+                .product();
+            scale_quote(quote, factor, 1.0 / factor)
+        })
+        .collect()
+}
+
+/// Back-adjusts prices before each ex-dividend date by `1 - amount / previous close`,
+/// the same total-return convention Yahoo uses for its adjusted close.
+pub fn adjust_for_dividends(quotes: &[CustomQuote], dividends: &[Dividend]) -> Vec<CustomQuote> {
+    let factors: Vec<(Dividend, f64)> = dividends
+        .iter()
+        .filter_map(|dividend| {
+            let previous_close = quotes
+                .iter()
+                .rev()
+                .find(|quote| quote.time < dividend.date)?
+                .close?;
+            if previous_close <= 0.0 || dividend.amount >= previous_close {
+                return None;
+            }
+            Some((dividend.clone(), 1.0 - dividend.amount / previous_close))
+        })
+        .collect();
+
+    quotes
+        .iter()
+        .map(|quote| {
+            let factor: f64 = factors
+                .iter()
+                .filter(|(dividend, _)| quote.time < dividend.date)
+                .map(|(_, factor)| factor)
+                .product();
+            scale_quote(quote, factor, 1.0)
+        })
+        .collect()
+}
+
+pub fn adjust_quotes(quotes: &[CustomQuote], events: &EventsData, adjustment: Adjustment) -> Vec<CustomQuote> {
+    match adjustment {
+        Adjustment::None => quotes.to_vec(),
+        Adjustment::Splits => adjust_for_splits(quotes, &events.splits),
+        Adjustment::SplitsAndDividends => {
+            adjust_for_dividends(&adjust_for_splits(quotes, &events.splits), &events.dividends)
+        }
+    }
+}
+
+/// Fetches the symbol's splits and dividends over the span of `quotes` from the current
+/// provider and adjusts them.
+pub async fn fetch_adjusted_quotes(
+    symbol: &str,
+    quotes: Vec<CustomQuote>,
+    adjustment: Adjustment,
+) -> Result<Vec<CustomQuote>, String> {
+    adjust_provider_quotes(provider().as_ref(), symbol, quotes, adjustment).await
+}
+
+/// Adjusts bars that came from `provider`. Splits are skipped when its bars are already
+/// split-adjusted, so pre-split prices aren't divided twice.
+pub async fn adjust_provider_quotes(
+    provider: &dyn MarketDataProvider,
+    symbol: &str,
+    quotes: Vec<CustomQuote>,
+    adjustment: Adjustment,
+) -> Result<Vec<CustomQuote>, String> {
+    let split_adjusted = provider.bars_split_adjusted();
+    if adjustment == Adjustment::None || quotes.is_empty() || (adjustment == Adjustment::Splits && split_adjusted) {
+        return Ok(quotes);
+    }
+    let from = quotes.first().unwrap().time;
+    let to = quotes.last().unwrap().time + Duration::days(1);
+    let mut events = provider.fetch_corporate_actions(symbol, from, to).await?.events;
+    if split_adjusted {
+        events.splits.clear();
+    }
+    Ok(adjust_quotes(&quotes, &events, adjustment))
+}
+
+fn scale_quote(quote: &CustomQuote, price_factor: f64, volume_factor: f64) -> CustomQuote {
+    CustomQuote {
+        high: quote.high.map(|price| price * price_factor),
+        volume: quote.volume.map(|volume| (volume as f64 * volume_factor).round() as u64),
+        open: quote.open.map(|price| price * price_factor),
+        low: quote.low.map(|price| price * price_factor),
+        close: quote.close.map(|price| price * price_factor),
+        time: quote.time,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::api_provider::ApiProvider;
+    use crate::providers::memory_provider::MemoryProvider;
+    use crate::types::CorporateActions;
+    use chrono::{DateTime, TimeZone, Utc};
+
+    fn bar(time: DateTime<Utc>, close: f64, volume: u64) -> CustomQuote {
+        CustomQuote {
+            high: Some(close),
+            volume: Some(volume),
+            open: Some(close),
+            low: Some(close),
+            close: Some(close),
+            time,
+        }
+    }
+
+    #[test]
+    fn test_split_adjustment_removes_gap() {
+        let start = Utc.with_ymd_and_hms(2020, 8, 27, 0, 0, 0).unwrap();
+        let quotes = vec![
+            bar(start, 500.0, 100),
+            bar(start + Duration::days(1), 496.0, 100),
+            bar(start + Duration::days(4), 128.0, 400),
+        ];
+        let splits = vec![Split {
+            date: start + Duration::days(4),
+            numerator: 4.0,
+            denominator: 1.0,
+        }];
+
+        let adjusted = adjust_for_splits(&quotes, &splits);
+        assert_eq!(adjusted[0].close, Some(125.0));
+        assert_eq!(adjusted[0].volume, Some(400));
+        assert_eq!(adjusted[1].close, Some(124.0));
+        assert_eq!(adjusted[2], quotes[2]);
+    }
+
+    #[test]
+    fn test_dividend_adjustment_uses_previous_close() {
+        let start = Utc.with_ymd_and_hms(2021, 2, 4, 0, 0, 0).unwrap();
+        let quotes = vec![
+            bar(start, 100.0, 10),
+            bar(start + Duration::days(1), 50.0, 10),
+            bar(start + Duration::days(2), 49.0, 10),
+        ];
+        let events = EventsData {
+            dividends: vec![Dividend {
+                date: start + Duration::days(2),
+                amount: 1.0,
+            }],
+            splits: vec![Split {
+                date: start + Duration::days(1),
+                numerator: 2.0,
+                denominator: 1.0,
+            }],
+        };
+
+        let adjusted = adjust_quotes(&quotes, &events, Adjustment::SplitsAndDividends);
+        assert!((adjusted[0].close.unwrap() - 49.0).abs() < 1e-9);
+        assert!((adjusted[1].close.unwrap() - 49.0).abs() < 1e-9);
+        assert_eq!(adjusted[2].close, Some(49.0));
+        assert_eq!(adjust_quotes(&quotes, &events, Adjustment::None), quotes);
+    }
+
+    #[tokio::test]
+    async fn test_split_adjusted_providers_are_not_adjusted_twice() {
+        let start = Utc.with_ymd_and_hms(2020, 8, 27, 0, 0, 0).unwrap();
+        let quotes = vec![bar(start, 500.0, 100), bar(start + Duration::days(4), 128.0, 400)];
+        let memory = MemoryProvider::new();
+        memory.insert_corporate_actions(
+            "AAPL",
+            CorporateActions {
+                events: EventsData {
+                    dividends: Vec::new(),
+                    splits: vec![Split {
+                        date: start + Duration::days(4),
+                        numerator: 4.0,
+                        denominator: 1.0,
+                    }],
+                },
+                ..Default::default()
+            },
+        );
+
+        let raw = adjust_provider_quotes(&memory, "AAPL", quotes.clone(), Adjustment::Splits).await.unwrap();
+        assert_eq!(raw[0].close, Some(125.0));
+
+        // Never reaches the network: Yahoo bars are already split-adjusted.
+        let api = ApiProvider::new("http://127.0.0.1:9");
+        let adjusted = adjust_provider_quotes(&api, "AAPL", quotes.clone(), Adjustment::Splits).await.unwrap();
+        assert_eq!(adjusted, quotes);
+    }
+}
@@ -1,68 +1,286 @@
-use crate::types::{CustomQuote, StrategyResult};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::{CustomQuote, ExitReason, StrategyResult, Timeframe, Trade, TradeSide};
+use crate::utils::benchmark_utils::compare;
+use crate::utils::costs_utils::{fill_order, CommissionModel, Fill, SlippageModel};
+use crate::utils::exit_utils::{ExitContext, ExitRules};
+use crate::utils::indicator_utils::calculate_atr;
+use crate::utils::metrics_utils::calculate_metrics;
+use crate::utils::order_utils::{OrderConfig, PendingOrder};
+use crate::utils::sizing_utils::PositionSizing;
 
-const COMMISSION: f64 = 0.5;
 const INITIAL_CAPITAL: f64 = 100000.0;
-const LOT_SIZE: f64 = 20.0;
-
-pub fn calculate_performance(data: &[CustomQuote], signals: &[String]) -> StrategyResult {
-    let mut capital = INITIAL_CAPITAL;
-    let mut num_trades = 0;
-    let mut winning_trades = 0;
-    let mut losing_trades = 0;
-    let mut profit = 0.0;
-    let mut loss = 0.0;
-    let mut position = 0.0;
-    let mut position_type = "";
-
-    for i in 1..signals.len() {
-        match signals[i].as_str() {
-            "buy" if position == 0.0 => {
-                position = LOT_SIZE;
-                position_type = "long";
-                capital -= position * data[i].close.unwrap_or(0.0) + COMMISSION;
-                num_trades += 1;
+
+/// Per-run backtest settings; every field falls back to its default when omitted.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct BacktestConfig {
+    pub sizing: PositionSizing,
+    pub commission: CommissionModel,
+    pub slippage: SlippageModel,
+    pub exits: ExitRules,
+    pub orders: OrderConfig,
+}
+
+impl BacktestConfig {
+    fn fill(&self, is_buy: bool, price: f64, quantity: f64, quote: &CustomQuote) -> Fill {
+        fill_order(&self.commission, &self.slippage, is_buy, price, quantity, quote)
+    }
+}
+
+struct OpenTrade {
+    side: TradeSide,
+    entry_index: usize,
+    entry: Fill,
+    entry_atr: Option<f64>,
+    quantity: f64,
+    lowest: f64,
+    highest: f64,
+}
+
+impl OpenTrade {
+    fn open(side: TradeSide, index: usize, entry: Fill, entry_atr: Option<f64>, quantity: f64) -> Self {
+        Self {
+            side,
+            entry_index: index,
+            entry,
+            entry_atr,
+            quantity,
+            lowest: entry.price,
+            highest: entry.price,
+        }
+    }
+
+    /// Shares held, negative while short.
+    fn signed_quantity(&self) -> f64 {
+        match self.side {
+            TradeSide::Long => self.quantity,
+            TradeSide::Short => -self.quantity,
+        }
+    }
+
+    fn track(&mut self, quote: &CustomQuote) {
+        let close = quote.close.unwrap_or(self.entry.price);
+        self.lowest = self.lowest.min(quote.low.unwrap_or(close));
+        self.highest = self.highest.max(quote.high.unwrap_or(close));
+    }
+
+    /// Like `track`, for a bar the trade was stopped out in: only the open and the exit
+    /// price are known to have traded while the position was held.
+    fn track_until(&mut self, quote: &CustomQuote, exit_price: f64) {
+        let open = quote.open.unwrap_or(exit_price);
+        self.lowest = self.lowest.min(open).min(exit_price);
+        self.highest = self.highest.max(open).max(exit_price);
+    }
+
+    fn exit_context(&self, index: usize) -> ExitContext {
+        ExitContext {
+            side: self.side,
+            entry_price: self.entry.price,
+            entry_atr: self.entry_atr,
+            best_price: match self.side {
+                TradeSide::Long => self.highest,
+                TradeSide::Short => self.lowest,
+            },
+            bars_held: index - self.entry_index,
+        }
+    }
+
+    fn close(self, index: usize, exit: Fill, reason: ExitReason, data: &[CustomQuote]) -> Trade {
+        let direction = self.signed_quantity();
+        let gross_pnl = direction * (exit.price - self.entry.price);
+        let (worst, best) = match self.side {
+            TradeSide::Long => (self.lowest, self.highest),
+            TradeSide::Short => (self.highest, self.lowest),
+        };
+        let commission = self.entry.commission + exit.commission;
+
+        Trade {
+            side: self.side,
+            entry_time: data[self.entry_index].time.to_string(),
+            entry_price: self.entry.price,
+            exit_time: data[index].time.to_string(),
+            exit_price: exit.price,
+            exit_reason: reason,
+            quantity: self.quantity,
+            commission,
+            slippage: self.entry.slippage + exit.slippage,
+            gross_pnl,
+            net_pnl: gross_pnl - commission,
+            bars_held: index - self.entry_index,
+            mae: (direction * (worst - self.entry.price)).min(0.0),
+            mfe: (direction * (best - self.entry.price)).max(0.0),
+        }
+    }
+}
+
+/// Cash, open position and closed trades while replaying a backtest.
+struct Account<'a> {
+    config: &'a BacktestConfig,
+    data: &'a [CustomQuote],
+    capital: f64,
+    trades: Vec<Trade>,
+    open_trade: Option<OpenTrade>,
+}
+
+impl Account<'_> {
+    fn position(&self) -> f64 {
+        self.open_trade.as_ref().map_or(0.0, |trade| trade.signed_quantity())
+    }
+
+    fn enter(&mut self, side: TradeSide, index: usize, price: f64, quantity: f64, entry_atr: Option<f64>) {
+        let entry = self.config.fill(side == TradeSide::Long, price, quantity, &self.data[index]);
+        let trade = OpenTrade::open(side, index, entry, entry_atr, quantity);
+        self.capital -= trade.signed_quantity() * entry.price + entry.commission;
+        self.open_trade = Some(trade);
+    }
+
+    fn exit(&mut self, index: usize, price: f64, reason: ExitReason) {
+        if let Some(trade) = self.open_trade.take() {
+            let exit = self.config.fill(trade.side == TradeSide::Short, price, trade.quantity, &self.data[index]);
+            self.capital += trade.signed_quantity() * exit.price - exit.commission;
+            self.trades.push(trade.close(index, exit, reason, self.data));
+        }
+    }
+}
+
+/// Replays `signals` over `data`. A "buy" opens a long or closes a short, a "sell" opens a
+/// short or closes a long; each becomes an order of `config.orders` type, filled after
+/// slippage on the signal bar's close or a later bar. Protective exits in `config.exits`
+/// are checked against each bar's range first; after one fires, signals in the direction of
+/// the closed trade are ignored until the signal changes. A position still open on the last
+/// bar is closed at its close.
+pub fn calculate_performance(
+    data: &[CustomQuote],
+    signals: &[String],
+    timeframe: Timeframe,
+    config: &BacktestConfig,
+) -> StrategyResult {
+    let mut account = Account {
+        config,
+        data,
+        capital: INITIAL_CAPITAL,
+        trades: Vec::new(),
+        open_trade: None,
+    };
+    let mut pending_order: Option<(PendingOrder, bool)> = None;
+    // Side of the last trade closed by a protective exit, while its signal still stands.
+    let mut protective_exit: Option<TradeSide> = None;
+    let mut equity_curve = Vec::with_capacity(data.len());
+    let mut positions = Vec::with_capacity(data.len());
+    if !data.is_empty() {
+        equity_curve.push(INITIAL_CAPITAL);
+        positions.push(0.0);
+    }
+    let atr_series = |period: usize| -> Vec<f64> {
+        calculate_atr(data, period).into_iter().map(|point| point.value).collect()
+    };
+    let sizing_atr = config.sizing.atr_period().map(atr_series);
+    let stop_atr = config.exits.atr_stop.map(|stop| atr_series(stop.period));
+
+    // Executes an order signalled on `signal_index` at `price` on bar `index`: it closes
+    // the open trade if it points the other way, otherwise opens a new one.
+    let execute = |account: &mut Account, is_buy: bool, signal_index: usize, index: usize, price: f64, equity: f64| {
+        match account.open_trade.as_ref().map(|trade| trade.side) {
+            Some(TradeSide::Long) if !is_buy => account.exit(index, price, ExitReason::Signal),
+            Some(TradeSide::Short) if is_buy => account.exit(index, price, ExitReason::Signal),
+            None => {
+                let side = if is_buy { TradeSide::Long } else { TradeSide::Short };
+                let atr_value = sizing_atr.as_ref().map(|atr| atr[signal_index]);
+                let quantity = config.sizing.quantity(equity, price, atr_value, &account.trades);
+                if quantity > 0.0 {
+                    let entry_atr = stop_atr.as_ref().map(|atr| atr[signal_index]);
+                    account.enter(side, index, price, quantity, entry_atr);
+                }
             }
-            "sell" if position == 0.0 => {
-                position = LOT_SIZE;
-                position_type = "short";
-                capital -= position * data[i].close.unwrap_or(0.0) + COMMISSION;
-                num_trades += 1;
+            _ => {}
+        }
+    };
+
+    for i in 1..signals.len().min(data.len()) {
+        let equity = equity_curve.last().copied().unwrap_or(account.capital);
+
+        if let Some(mut trade) = account.open_trade.take() {
+            match config.exits.check(&trade.exit_context(i), &data[i]) {
+                Some((exit_price, reason)) => {
+                    trade.track_until(&data[i], exit_price);
+                    protective_exit = Some(trade.side);
+                    account.open_trade = Some(trade);
+                    account.exit(i, exit_price, reason);
+                }
+                None => {
+                    trade.track(&data[i]);
+                    account.open_trade = Some(trade);
+                }
             }
-            "sell" if position != 0.0 && position_type == "long" => {
-                capital += position * data[i].close.unwrap_or(0.0) - COMMISSION;
-                let trade_profit = position * (data[i].close.unwrap_or(0.0) - data[i - 1].close.unwrap_or(0.0)) - 2.0 * COMMISSION;
-                if trade_profit > 0.0 {
-                    winning_trades += 1;
-                    profit += trade_profit;
-                } else {
-                    losing_trades += 1;
-                    loss -= trade_profit;
+        }
+
+        // A working exit order is cancelled if a protective exit closed the trade first.
+        if let Some((mut order, closes)) = pending_order.take() {
+            let cancelled = order.is_expired(i, config.orders.expiry_bars) || (closes && account.open_trade.is_none());
+            if !cancelled {
+                match order.try_fill(&data[i]) {
+                    Some(price) => {
+                        execute(&mut account, order.is_buy, order.placed_index, i, price, equity);
+                        // A trade opened during this bar saw the rest of its range.
+                        if let Some(trade) = account.open_trade.as_mut().filter(|trade| trade.entry_index == i) {
+                            trade.track(&data[i]);
+                        }
+                    }
+                    None => pending_order = Some((order, closes)),
                 }
-                position = 0.0;
-                position_type = "";
             }
-            "buy" if position != 0.0 && position_type == "short" => {
-                capital += position * data[i].close.unwrap_or(0.0) - COMMISSION;
-                let trade_profit = position * (data[i - 1].close.unwrap_or(0.0) - data[i].close.unwrap_or(0.0)) - 2.0 * COMMISSION;
-                if trade_profit > 0.0 {
-                    winning_trades += 1;
-                    profit += trade_profit;
-                } else {
-                    losing_trades += 1;
-                    loss -= trade_profit;
+        }
+
+        let price = data[i].close.unwrap_or(0.0);
+        let mut is_buy = match signals[i].as_str() {
+            "buy" => Some(true),
+            "sell" => Some(false),
+            _ => None,
+        };
+        let exited_side = protective_exit.map(|side| side == TradeSide::Long);
+        if is_buy.is_some() && is_buy == exited_side {
+            is_buy = None;
+        } else {
+            protective_exit = None;
+        }
+        let side = account.open_trade.as_ref().map(|trade| trade.side);
+        let actionable = match (is_buy, side) {
+            (Some(is_buy), None) => Some((is_buy, false)),
+            (Some(is_buy), Some(side)) if is_buy == (side == TradeSide::Short) => Some((is_buy, true)),
+            _ => None,
+        };
+        // Repeated signals leave a working order in the same direction untouched.
+        if let Some((is_buy, closes)) = actionable {
+            if pending_order.as_ref().is_none_or(|(order, _)| order.is_buy != is_buy) {
+                pending_order = PendingOrder::place(config.orders.order_type, is_buy, price, i).map(|order| (order, closes));
+                if pending_order.is_none() {
+                    execute(&mut account, is_buy, i, i, price, equity);
                 }
-                position = 0.0;
-                position_type = "";
             }
-            _ => {}
         }
+
+        let position = account.position();
+        equity_curve.push(account.capital + position * price);
+        positions.push(position);
     }
 
-    let final_capital = if position != 0.0 {
-        capital + position * data.last().unwrap().close.unwrap_or(0.0) - COMMISSION
-    } else {
-        capital
-    };
+    if account.open_trade.is_some() {
+        let last = equity_curve.len() - 1;
+        account.exit(last, data[last].close.unwrap_or(0.0), ExitReason::EndOfData);
+    }
+    let Account { capital, trades, .. } = account;
+    let final_capital = capital;
+
+    let num_trades = trades.len();
+    let total_commission: f64 = trades.iter().map(|trade| trade.commission).sum();
+    let total_slippage: f64 = trades.iter().map(|trade| trade.slippage).sum();
+    let winning_trades = trades.iter().filter(|trade| trade.net_pnl > 0.0).count();
+    let losing_trades = num_trades - winning_trades;
+    let profit: f64 = trades.iter().map(|trade| trade.net_pnl.max(0.0)).sum();
+    let loss: f64 = trades.iter().map(|trade| (-trade.net_pnl).max(0.0)).sum();
 
     let winning_percentage = if num_trades > 0 {
         (winning_trades as f64 / num_trades as f64) * 100.0
@@ -78,6 +296,27 @@ pub fn calculate_performance(data: &[CustomQuote], signals: &[String]) -> Strate
 
     let total_return_percentage = ((final_capital / INITIAL_CAPITAL) - 1.0) * 100.0;
 
+    if let Some(last) = equity_curve.last_mut() {
+        *last = final_capital;
+    }
+    let drawdown = drawdown_series(&equity_curve);
+    let stats = drawdown_stats(&drawdown);
+    let date_at = |index: Option<usize>| index.map(|i| data[i].time.to_string());
+    let closes: Vec<f64> = data[..equity_curve.len()].iter().map(|quote| quote.close.unwrap_or(0.0)).collect();
+    let buy_and_hold = compare(&equity_curve, &closes, timeframe, "");
+    let metrics = match (data.first(), equity_curve.len().checked_sub(1)) {
+        (Some(first), Some(last)) => calculate_metrics(
+            &equity_curve,
+            &positions,
+            &trades,
+            stats.max_drawdown,
+            timeframe,
+            first.time,
+            data[last].time,
+        ),
+        _ => Default::default(),
+    };
+
     StrategyResult {
         signals: signals.to_vec(),
         dates: data.iter().map(|q| q.time.to_string()).collect(),
@@ -88,5 +327,285 @@ pub fn calculate_performance(data: &[CustomQuote], signals: &[String]) -> Strate
         profit_factor,
         final_capital,
         total_return_percentage,
+        max_drawdown_percentage: stats.max_drawdown,
+        max_drawdown_peak: date_at(stats.peak),
+        max_drawdown_trough: date_at(stats.trough),
+        max_drawdown_recovery: date_at(stats.recovery),
+        max_bars_under_water: stats.max_bars_under_water,
+        equity_curve,
+        drawdown,
+        total_commission,
+        total_slippage,
+        total_costs: total_commission + total_slippage,
+        trades,
+        metrics,
+        buy_and_hold,
+        benchmark: None,
+    }
+}
+
+/// Writes the trade ledger as CSV, one row per trade with a header row.
+pub fn export_trades_csv(path: &Path, trades: &[Trade]) -> Result<(), String> {
+    let mut writer = csv::Writer::from_path(path).map_err(|e| e.to_string())?;
+    for trade in trades {
+        writer.serialize(trade).map_err(|e| e.to_string())?;
+    }
+    writer.flush().map_err(|e| e.to_string())
+}
+
+/// Percentage below the running equity peak at each bar (0 at a new high, negative below it).
+pub fn drawdown_series(equity: &[f64]) -> Vec<f64> {
+    let mut peak = f64::MIN;
+    equity
+        .iter()
+        .map(|&value| {
+            peak = peak.max(value);
+            if peak > 0.0 {
+                (value / peak - 1.0) * 100.0
+            } else {
+                0.0
+            }
+        })
+        .collect()
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct DrawdownStats {
+    /// Deepest drawdown, as a negative percentage.
+    pub max_drawdown: f64,
+    pub peak: Option<usize>,
+    pub trough: Option<usize>,
+    /// First bar back at the peak after the deepest drawdown; `None` if it never recovered.
+    pub recovery: Option<usize>,
+    /// Longest run of consecutive bars below a previous peak.
+    pub max_bars_under_water: usize,
+}
+
+pub fn drawdown_stats(drawdown: &[f64]) -> DrawdownStats {
+    let mut stats = DrawdownStats::default();
+    let mut last_peak = 0;
+
+    for (i, &value) in drawdown.iter().enumerate() {
+        if value >= 0.0 {
+            last_peak = i;
+        } else {
+            stats.max_bars_under_water = stats.max_bars_under_water.max(i - last_peak);
+            if value < stats.max_drawdown {
+                stats.max_drawdown = value;
+                stats.peak = Some(last_peak);
+                stats.trough = Some(i);
+            }
+        }
+    }
+
+    if let Some(trough) = stats.trough {
+        stats.recovery = drawdown[trough..]
+            .iter()
+            .position(|&value| value >= 0.0)
+            .map(|offset| trough + offset);
+    }
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::order_utils::OrderType;
+    use chrono::{Duration, TimeZone, Utc};
+
+    fn bar(close: f64, low: f64, high: f64, day: i64) -> CustomQuote {
+        CustomQuote {
+            high: Some(high),
+            volume: Some(1000),
+            open: Some(close),
+            low: Some(low),
+            close: Some(close),
+            time: Utc.with_ymd_and_hms(2023, 1, 2, 0, 0, 0).unwrap() + Duration::days(day),
+        }
+    }
+
+    fn signals(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    fn on_close() -> BacktestConfig {
+        BacktestConfig {
+            orders: OrderConfig {
+                order_type: OrderType::MarketOnClose,
+                expiry_bars: None,
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_trade_ledger_uses_entry_price() {
+        let data = vec![
+            bar(10.0, 10.0, 10.0, 0),
+            bar(10.0, 9.5, 10.5, 1),
+            bar(12.0, 9.0, 13.0, 2),
+            bar(15.0, 14.0, 16.0, 3),
+            bar(14.0, 13.0, 17.0, 4),
+            bar(11.0, 10.0, 14.5, 5),
+        ];
+        let result = calculate_performance(&data, &signals(&["hold", "buy", "hold", "sell", "sell", "buy"]), Timeframe::OneDay, &on_close());
+
+        assert_eq!(result.trades.len(), 2);
+        let long = &result.trades[0];
+        assert_eq!(long.side, TradeSide::Long);
+        assert_eq!(long.bars_held, 2);
+        assert!((long.gross_pnl - 20.0 * 5.0).abs() < 1e-9);
+        assert!((long.net_pnl - (100.0 - 1.0)).abs() < 1e-9);
+        assert!((long.mae + 20.0).abs() < 1e-9);
+        assert!((long.mfe - 20.0 * 6.0).abs() < 1e-9);
+
+        let short = &result.trades[1];
+        assert_eq!(short.side, TradeSide::Short);
+        assert!((short.gross_pnl - 20.0 * 3.0).abs() < 1e-9);
+        assert!((short.mae + 10.0).abs() < 1e-9);
+        assert!((short.mfe - 20.0 * 4.0).abs() < 1e-9);
+
+        let net: f64 = result.trades.iter().map(|trade| trade.net_pnl).sum();
+        assert!((result.final_capital - INITIAL_CAPITAL - net).abs() < 1e-9);
+        assert_eq!(result.winning_trades, 2);
+    }
+
+    #[test]
+    fn test_open_position_closed_on_last_bar() {
+        let data = vec![bar(10.0, 10.0, 10.0, 0), bar(10.0, 10.0, 10.0, 1), bar(8.0, 8.0, 10.0, 2)];
+        let result = calculate_performance(&data, &signals(&["hold", "buy", "buy"]), Timeframe::OneDay, &on_close());
+        assert_eq!(result.trades.len(), 1);
+        assert_eq!(result.losing_trades, 1);
+        assert_eq!(result.trades[0].exit_price, 8.0);
+        assert_eq!(result.equity_curve.last(), Some(&result.final_capital));
+    }
+
+    #[test]
+    fn test_sizing_from_config() {
+        let data = vec![bar(50.0, 50.0, 50.0, 0), bar(50.0, 50.0, 50.0, 1), bar(60.0, 60.0, 60.0, 2)];
+        let config = BacktestConfig {
+            sizing: PositionSizing::PercentEquity { percent: 50.0 },
+            ..on_close()
+        };
+        let result = calculate_performance(&data, &signals(&["hold", "buy", "sell"]), Timeframe::OneDay, &config);
+        assert_eq!(result.trades[0].quantity, 1000.0);
+        assert!((result.trades[0].gross_pnl - 10_000.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_costs_reduce_final_capital() {
+        let data = vec![bar(50.0, 50.0, 50.0, 0), bar(50.0, 50.0, 50.0, 1), bar(60.0, 60.0, 60.0, 2)];
+        let config = BacktestConfig {
+            slippage: SlippageModel::FixedBps { bps: 100.0 },
+            ..on_close()
+        };
+        let result = calculate_performance(&data, &signals(&["hold", "buy", "sell"]), Timeframe::OneDay, &config);
+        let trade = &result.trades[0];
+        assert!((trade.entry_price - 50.5).abs() < 1e-9);
+        assert!((trade.exit_price - 59.4).abs() < 1e-9);
+        assert!((result.total_slippage - 20.0 * 1.1).abs() < 1e-9);
+        assert!((result.total_commission - 1.0).abs() < 1e-9);
+        assert!((result.final_capital - (INITIAL_CAPITAL + 200.0 - result.total_costs)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_stop_loss_exit_fills_at_level() {
+        let data = vec![
+            bar(100.0, 100.0, 100.0, 0),
+            bar(100.0, 100.0, 100.0, 1),
+            bar(97.0, 94.0, 101.0, 2),
+            bar(99.0, 98.0, 100.0, 3),
+        ];
+        let config = BacktestConfig {
+            exits: ExitRules {
+                stop_loss_percent: Some(5.0),
+                ..Default::default()
+            },
+            ..on_close()
+        };
+        let result = calculate_performance(&data, &signals(&["hold", "buy", "hold", "hold"]), Timeframe::OneDay, &config);
+        assert_eq!(result.trades.len(), 1);
+        let trade = &result.trades[0];
+        assert_eq!(trade.exit_reason, ExitReason::StopLoss);
+        assert_eq!(trade.exit_price, 95.0);
+        assert_eq!(trade.bars_held, 1);
+        assert!((trade.mae + 20.0 * 5.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_standing_signal_does_not_reenter_after_stop() {
+        let data = vec![
+            bar(100.0, 100.0, 100.0, 0),
+            bar(100.0, 100.0, 100.0, 1),
+            bar(97.0, 94.0, 101.0, 2),
+            bar(99.0, 98.0, 100.0, 3),
+            bar(101.0, 100.0, 102.0, 4),
+            bar(102.0, 101.0, 103.0, 5),
+        ];
+        let config = BacktestConfig {
+            exits: ExitRules {
+                stop_loss_percent: Some(5.0),
+                ..Default::default()
+            },
+            ..on_close()
+        };
+        let result = calculate_performance(&data, &signals(&["hold", "buy", "buy", "buy", "hold", "buy"]), Timeframe::OneDay, &config);
+        assert_eq!(result.trades.len(), 2);
+        assert_eq!(result.trades[0].exit_reason, ExitReason::StopLoss);
+        assert_eq!(&result.equity_curve[2..5], &[result.equity_curve[2]; 3]);
+        assert_eq!(result.trades[1].entry_time, data[5].time.to_string());
+    }
+
+    #[test]
+    fn test_drawdown_series_and_stats() {
+        let equity = [100.0, 110.0, 99.0, 88.0, 105.0, 110.0, 120.0, 114.0];
+        let drawdown = drawdown_series(&equity);
+        assert_eq!(drawdown[1], 0.0);
+        assert!((drawdown[3] + 20.0).abs() < 1e-9);
+        assert!((drawdown[7] + 5.0).abs() < 1e-9);
+
+        let stats = drawdown_stats(&drawdown);
+        assert!((stats.max_drawdown + 20.0).abs() < 1e-9);
+        assert_eq!(stats.peak, Some(1));
+        assert_eq!(stats.trough, Some(3));
+        assert_eq!(stats.recovery, Some(5));
+        assert_eq!(stats.max_bars_under_water, 3);
+    }
+
+    #[test]
+    fn test_no_drawdown() {
+        let stats = drawdown_stats(&drawdown_series(&[100.0, 101.0, 102.0]));
+        assert_eq!(stats, DrawdownStats::default());
+    }
+
+    #[test]
+    fn test_signals_fill_on_next_open_by_default() {
+        let mut data = vec![bar(10.0, 10.0, 10.0, 0), bar(10.0, 10.0, 10.0, 1), bar(12.0, 10.5, 12.5, 2), bar(13.0, 12.0, 14.0, 3)];
+        data[2].open = Some(11.0);
+        data[3].open = Some(12.5);
+        let result = calculate_performance(&data, &signals(&["hold", "buy", "sell", "hold"]), Timeframe::OneDay, &BacktestConfig::default());
+        let trade = &result.trades[0];
+        // The overnight gap from the signal close of 10 is part of the fill.
+        assert_eq!(trade.entry_price, 11.0);
+        // The entry bar dips to 10.5 after the open.
+        assert!((trade.mae + trade.quantity * 0.5).abs() < 1e-9);
+        assert_eq!(trade.exit_price, 12.5);
+        assert_eq!(trade.exit_reason, ExitReason::Signal);
+        assert_eq!(result.equity_curve[1], INITIAL_CAPITAL);
+    }
+
+    #[test]
+    fn test_limit_order_expires_unfilled() {
+        let data: Vec<CustomQuote> = (0..5).map(|day| bar(10.0, 9.9, 10.1, day)).collect();
+        let config = BacktestConfig {
+            orders: OrderConfig {
+                order_type: OrderType::Limit { offset_percent: 5.0 },
+                expiry_bars: Some(2),
+            },
+            ..Default::default()
+        };
+        let result = calculate_performance(&data, &signals(&["hold", "buy", "hold", "hold", "hold"]), Timeframe::OneDay, &config);
+        assert!(result.trades.is_empty());
+        assert_eq!(result.final_capital, INITIAL_CAPITAL);
     }
 }
@@ -0,0 +1,124 @@
+use crate::types::{BenchmarkComparison, CustomQuote, Timeframe};
+use crate::utils::metrics_utils::{bar_returns, covariance, mean, std_dev};
+
+/// The benchmark's close at each of `data`'s bar times: the latest benchmark bar at or
+/// before it, or the first benchmark bar for times before the benchmark starts.
+pub fn aligned_closes(data: &[CustomQuote], benchmark: &[CustomQuote]) -> Vec<f64> {
+    let closes: Vec<&CustomQuote> = benchmark.iter().filter(|quote| quote.close.is_some()).collect();
+    let mut next = 0;
+    data.iter()
+        .map(|quote| {
+            while next < closes.len() && closes[next].time <= quote.time {
+                next += 1;
+            }
+            closes
+                .get(next.saturating_sub(1))
+                .and_then(|quote| quote.close)
+                .unwrap_or(0.0)
+        })
+        .collect()
+}
+
+/// Compares a strategy's equity curve with holding the benchmark over the same bars.
+/// Alpha and tracking error are annualized percentages; beta and the information ratio
+/// come from per-bar returns.
+pub fn compare(equity: &[f64], benchmark_prices: &[f64], timeframe: Timeframe, symbol: &str) -> BenchmarkComparison {
+    let periods = timeframe.periods_per_year();
+    let strategy_returns = bar_returns(equity);
+    let benchmark_returns = bar_returns(benchmark_prices);
+    let active: Vec<f64> = strategy_returns
+        .iter()
+        .zip(&benchmark_returns)
+        .map(|(strategy, benchmark)| strategy - benchmark)
+        .collect();
+
+    let total_return = |series: &[f64]| match (series.first(), series.last()) {
+        (Some(&first), Some(&last)) if first > 0.0 => (last / first - 1.0) * 100.0,
+        _ => 0.0,
+    };
+    let strategy_return = total_return(equity);
+    let benchmark_return = total_return(benchmark_prices);
+
+    let benchmark_variance = std_dev(&benchmark_returns).powi(2);
+    let beta = if benchmark_variance > 0.0 {
+        covariance(&strategy_returns, &benchmark_returns) / benchmark_variance
+    } else {
+        0.0
+    };
+    let alpha = (mean(&strategy_returns) - beta * mean(&benchmark_returns)) * periods * 100.0;
+    let active_deviation = std_dev(&active);
+    let information_ratio = if active_deviation > 0.0 {
+        mean(&active) / active_deviation * periods.sqrt()
+    } else {
+        0.0
+    };
+
+    let relative_curve = match (equity.first(), benchmark_prices.first()) {
+        (Some(&equity_start), Some(&price_start)) if equity_start > 0.0 && price_start > 0.0 => equity
+            .iter()
+            .zip(benchmark_prices)
+            .map(|(value, price)| {
+                if *price > 0.0 {
+                    (value / equity_start) / (price / price_start)
+                } else {
+                    0.0
+                }
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    BenchmarkComparison {
+        symbol: symbol.to_string(),
+        total_return_percentage: benchmark_return,
+        excess_return_percentage: strategy_return - benchmark_return,
+        alpha,
+        beta,
+        information_ratio,
+        tracking_error: active_deviation * periods.sqrt() * 100.0,
+        relative_curve,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone, Utc};
+
+    #[test]
+    fn test_leveraged_benchmark_has_beta_two() {
+        let prices = [100.0, 102.0, 99.0, 103.0, 101.0, 104.0];
+        let mut equity = vec![1000.0];
+        for pair in prices.windows(2) {
+            let last = *equity.last().unwrap();
+            equity.push(last * (1.0 + 2.0 * (pair[1] / pair[0] - 1.0)));
+        }
+
+        let comparison = compare(&equity, &prices, Timeframe::OneDay, "SPY");
+        assert!((comparison.beta - 2.0).abs() < 1e-9);
+        assert!(comparison.alpha.abs() < 1e-6 * 252.0 * 100.0);
+        assert!((comparison.total_return_percentage - 4.0).abs() < 1e-9);
+        assert_eq!(comparison.relative_curve.len(), prices.len());
+        assert_eq!(comparison.relative_curve[0], 1.0);
+
+        let identical = compare(&prices, &prices, Timeframe::OneDay, "SPY");
+        assert_eq!(identical.tracking_error, 0.0);
+        assert_eq!(identical.excess_return_percentage, 0.0);
+    }
+
+    #[test]
+    fn test_aligned_closes_forward_fill() {
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let quote = |day: i64, close: f64| CustomQuote {
+            high: Some(close),
+            volume: Some(1),
+            open: Some(close),
+            low: Some(close),
+            close: Some(close),
+            time: start + Duration::days(day),
+        };
+        let data = vec![quote(0, 1.0), quote(1, 1.0), quote(2, 1.0), quote(4, 1.0)];
+        let benchmark = vec![quote(1, 10.0), quote(2, 11.0), quote(3, 12.0)];
+        assert_eq!(aligned_closes(&data, &benchmark), vec![10.0, 10.0, 11.0, 12.0]);
+    }
+}
@@ -0,0 +1,181 @@
+use serde::{Deserialize, Serialize};
+
+use crate::types::CustomQuote;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CommissionTier {
+    /// Smallest order size, in shares, the tier applies to.
+    pub from_quantity: f64,
+    /// Commission per share.
+    pub rate: f64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CommissionSchedule {
+    Flat { amount: f64 },
+    PerShare { rate: f64 },
+    PercentNotional { percent: f64 },
+    /// Per-share rate picked by order size from the tier with the largest `from_quantity`
+    /// not above the order's quantity.
+    Tiered { tiers: Vec<CommissionTier> },
+}
+
+impl Default for CommissionSchedule {
+    fn default() -> Self {
+        CommissionSchedule::Flat { amount: 0.5 }
+    }
+}
+
+/// Commission per order (each side of a trade is one order), clamped to `minimum`/`maximum`.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct CommissionModel {
+    pub schedule: CommissionSchedule,
+    pub minimum: f64,
+    pub maximum: Option<f64>,
+}
+
+impl CommissionModel {
+    pub fn commission(&self, price: f64, quantity: f64) -> f64 {
+        let commission = match &self.schedule {
+            CommissionSchedule::Flat { amount } => *amount,
+            CommissionSchedule::PerShare { rate } => rate * quantity,
+            CommissionSchedule::PercentNotional { percent } => price * quantity * percent / 100.0,
+            CommissionSchedule::Tiered { tiers } => {
+                let rate = tiers
+                    .iter()
+                    .filter(|tier| tier.from_quantity <= quantity)
+                    .max_by(|a, b| a.from_quantity.total_cmp(&b.from_quantity))
+                    .map_or(0.0, |tier| tier.rate);
+                rate * quantity
+            }
+        };
+        let commission = commission.max(self.minimum);
+        self.maximum.map_or(commission, |maximum| commission.min(maximum))
+    }
+}
+
+/// Adverse price movement applied to every fill: buys fill higher, sells lower.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SlippageModel {
+    #[default]
+    None,
+    FixedBps { bps: f64 },
+    /// Pays `fraction` of the bar's high-low range, a stand-in for the spread when only
+    /// OHLC bars are available.
+    SpreadFraction { fraction: f64 },
+    /// Square-root market impact: `impact_bps * sqrt(quantity / bar volume)`, so trading
+    /// the whole bar's volume costs `impact_bps`.
+    VolumeParticipation { impact_bps: f64 },
+}
+
+impl SlippageModel {
+    /// Slippage per share for an order of `quantity` at `price` within `quote`.
+    pub fn slippage(&self, price: f64, quantity: f64, quote: &CustomQuote) -> f64 {
+        match self {
+            SlippageModel::None => 0.0,
+            SlippageModel::FixedBps { bps } => price * bps / 10_000.0,
+            SlippageModel::SpreadFraction { fraction } => match (quote.high, quote.low) {
+                (Some(high), Some(low)) => (high - low).max(0.0) * fraction,
+                _ => 0.0,
+            },
+            SlippageModel::VolumeParticipation { impact_bps } => match quote.volume {
+                Some(volume) if volume > 0 => {
+                    price * impact_bps / 10_000.0 * (quantity / volume as f64).sqrt()
+                }
+                _ => 0.0,
+            },
+        }
+    }
+}
+
+/// An executed order. `slippage` and `commission` are totals in account currency.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Fill {
+    pub price: f64,
+    pub commission: f64,
+    pub slippage: f64,
+}
+
+pub fn fill_order(
+    commission: &CommissionModel,
+    slippage: &SlippageModel,
+    is_buy: bool,
+    price: f64,
+    quantity: f64,
+    quote: &CustomQuote,
+) -> Fill {
+    let per_share = slippage.slippage(price, quantity, quote);
+    let fill_price = if is_buy { price + per_share } else { price - per_share };
+    Fill {
+        price: fill_price,
+        commission: commission.commission(fill_price, quantity),
+        slippage: per_share * quantity,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn quote(high: f64, low: f64, volume: u64) -> CustomQuote {
+        CustomQuote {
+            high: Some(high),
+            volume: Some(volume),
+            open: Some(low),
+            low: Some(low),
+            close: Some(high),
+            time: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_commission_models() {
+        let per_share = CommissionModel {
+            schedule: CommissionSchedule::PerShare { rate: 0.005 },
+            minimum: 1.0,
+            maximum: Some(20.0),
+        };
+        assert_eq!(per_share.commission(50.0, 100.0), 1.0);
+        assert_eq!(per_share.commission(50.0, 1000.0), 5.0);
+        assert_eq!(per_share.commission(50.0, 10_000.0), 20.0);
+
+        let percent = CommissionModel {
+            schedule: CommissionSchedule::PercentNotional { percent: 0.1 },
+            ..Default::default()
+        };
+        assert!((percent.commission(50.0, 100.0) - 5.0).abs() < 1e-9);
+
+        let tiered = CommissionModel {
+            schedule: CommissionSchedule::Tiered {
+                tiers: vec![
+                    CommissionTier { from_quantity: 0.0, rate: 0.01 },
+                    CommissionTier { from_quantity: 500.0, rate: 0.005 },
+                ],
+            },
+            ..Default::default()
+        };
+        assert_eq!(tiered.commission(10.0, 100.0), 1.0);
+        assert_eq!(tiered.commission(10.0, 1000.0), 5.0);
+        assert_eq!(CommissionModel::default().commission(10.0, 1000.0), 0.5);
+    }
+
+    #[test]
+    fn test_slippage_is_adverse() {
+        let bar = quote(101.0, 99.0, 10_000);
+        let fixed = SlippageModel::FixedBps { bps: 10.0 };
+        let buy = fill_order(&CommissionModel::default(), &fixed, true, 100.0, 10.0, &bar);
+        let sell = fill_order(&CommissionModel::default(), &fixed, false, 100.0, 10.0, &bar);
+        assert!((buy.price - 100.1).abs() < 1e-9);
+        assert!((sell.price - 99.9).abs() < 1e-9);
+        assert!((buy.slippage - 1.0).abs() < 1e-9);
+
+        let spread = SlippageModel::SpreadFraction { fraction: 0.25 };
+        assert!((spread.slippage(100.0, 10.0, &bar) - 0.5).abs() < 1e-9);
+        let impact = SlippageModel::VolumeParticipation { impact_bps: 100.0 };
+        assert!((impact.slippage(100.0, 2_500.0, &bar) - 0.5).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,168 @@
+use serde::{Deserialize, Serialize};
+
+use crate::types::{CustomQuote, ExitReason, TradeSide};
+
+/// Which exit wins when a bar's range touches both the stop and the target and the open
+/// is between them (OHLC bars don't say which came first).
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum IntrabarFill {
+    /// Assume the worst case: the stop filled first.
+    #[default]
+    StopFirst,
+    TargetFirst,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct AtrStop {
+    pub period: usize,
+    pub multiple: f64,
+}
+
+/// Protective exits checked on every bar after the entry bar. Percentages are relative to
+/// the entry price; the ATR stop uses the ATR at entry. When several stops are set the
+/// tightest one applies.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct ExitRules {
+    pub stop_loss_percent: Option<f64>,
+    pub atr_stop: Option<AtrStop>,
+    pub trailing_stop_percent: Option<f64>,
+    pub take_profit_percent: Option<f64>,
+    /// Close at the bar's close once the trade has been held this many bars.
+    pub max_bars: Option<usize>,
+    pub intrabar_fill: IntrabarFill,
+}
+
+/// Snapshot of an open trade the exit rules are evaluated against.
+pub struct ExitContext {
+    pub side: TradeSide,
+    pub entry_price: f64,
+    pub entry_atr: Option<f64>,
+    /// Best price reached before the current bar (highest high long, lowest low short).
+    pub best_price: f64,
+    pub bars_held: usize,
+}
+
+impl ExitRules {
+    fn stop_level(&self, context: &ExitContext) -> Option<(f64, ExitReason)> {
+        let direction = match context.side {
+            TradeSide::Long => 1.0,
+            TradeSide::Short => -1.0,
+        };
+        let entry = context.entry_price;
+        let candidates = [
+            self.stop_loss_percent
+                .map(|percent| (entry * (1.0 - direction * percent / 100.0), ExitReason::StopLoss)),
+            self.atr_stop
+                .zip(context.entry_atr)
+                .map(|(stop, atr)| (entry - direction * stop.multiple * atr, ExitReason::StopLoss)),
+            self.trailing_stop_percent.map(|percent| {
+                (context.best_price * (1.0 - direction * percent / 100.0), ExitReason::TrailingStop)
+            }),
+        ];
+        // The tightest stop is the highest for a long and the lowest for a short.
+        candidates
+            .into_iter()
+            .flatten()
+            .max_by(|a, b| (direction * a.0).total_cmp(&(direction * b.0)))
+    }
+
+    fn target_level(&self, context: &ExitContext) -> Option<f64> {
+        self.take_profit_percent.map(|percent| match context.side {
+            TradeSide::Long => context.entry_price * (1.0 + percent / 100.0),
+            TradeSide::Short => context.entry_price * (1.0 - percent / 100.0),
+        })
+    }
+
+    /// Returns the exit price and reason if `quote` triggers an exit. A bar that opens
+    /// through a level fills at the open; otherwise at the level itself.
+    pub fn check(&self, context: &ExitContext, quote: &CustomQuote) -> Option<(f64, ExitReason)> {
+        let close = quote.close?;
+        let open = quote.open.unwrap_or(close);
+        let high = quote.high.unwrap_or(close);
+        let low = quote.low.unwrap_or(close);
+        let is_long = context.side == TradeSide::Long;
+
+        let stop = self.stop_level(context).and_then(|(level, reason)| {
+            let hit = if is_long { low <= level } else { high >= level };
+            let gapped = if is_long { open <= level } else { open >= level };
+            hit.then_some((if gapped { open } else { level }, reason, gapped))
+        });
+        let target = self.target_level(context).and_then(|level| {
+            let hit = if is_long { high >= level } else { low <= level };
+            let gapped = if is_long { open >= level } else { open <= level };
+            hit.then_some((if gapped { open } else { level }, ExitReason::TakeProfit, gapped))
+        });
+
+        let triggered = match (stop, target) {
+            (Some(stop), Some(target)) => {
+                if stop.2 || (!target.2 && self.intrabar_fill == IntrabarFill::StopFirst) {
+                    Some(stop)
+                } else {
+                    Some(target)
+                }
+            }
+            (stop, target) => stop.or(target),
+        };
+        if let Some((price, reason, _)) = triggered {
+            return Some((price, reason));
+        }
+
+        match self.max_bars {
+            Some(max_bars) if context.bars_held >= max_bars => Some((close, ExitReason::MaxBars)),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::test_support::quote;
+
+    fn long_context(best_price: f64) -> ExitContext {
+        ExitContext {
+            side: TradeSide::Long,
+            entry_price: 100.0,
+            entry_atr: Some(2.0),
+            best_price,
+            bars_held: 1,
+        }
+    }
+
+    #[test]
+    fn test_tightest_stop_and_gaps() {
+        let rules = ExitRules {
+            stop_loss_percent: Some(5.0),
+            atr_stop: Some(AtrStop { period: 14, multiple: 2.0 }),
+            ..Default::default()
+        };
+        let context = long_context(100.0);
+        assert_eq!(rules.check(&context, &quote(99.0, 100.0, 95.5, 96.0)), Some((96.0, ExitReason::StopLoss)));
+        assert_eq!(rules.check(&context, &quote(93.0, 94.0, 90.0, 91.0)), Some((93.0, ExitReason::StopLoss)));
+        assert_eq!(rules.check(&context, &quote(99.0, 101.0, 97.0, 100.0)), None);
+
+        let trailing = ExitRules { trailing_stop_percent: Some(10.0), ..Default::default() };
+        assert_eq!(trailing.check(&long_context(120.0), &quote(115.0, 116.0, 107.0, 110.0)), Some((108.0, ExitReason::TrailingStop)));
+    }
+
+    #[test]
+    fn test_fill_rule_when_both_levels_hit() {
+        let mut rules = ExitRules {
+            stop_loss_percent: Some(5.0),
+            take_profit_percent: Some(5.0),
+            ..Default::default()
+        };
+        let wide_bar = quote(100.0, 106.0, 94.0, 100.0);
+        assert_eq!(rules.check(&long_context(100.0), &wide_bar), Some((95.0, ExitReason::StopLoss)));
+        rules.intrabar_fill = IntrabarFill::TargetFirst;
+        assert_eq!(rules.check(&long_context(100.0), &wide_bar), Some((105.0, ExitReason::TakeProfit)));
+
+        let short = ExitContext { side: TradeSide::Short, ..long_context(100.0) };
+        assert_eq!(rules.check(&short, &quote(94.0, 106.0, 93.0, 100.0)), Some((94.0, ExitReason::TakeProfit)));
+
+        let timed = ExitRules { max_bars: Some(1), ..Default::default() };
+        assert_eq!(timed.check(&long_context(100.0), &quote(100.0, 101.0, 99.0, 100.5)), Some((100.5, ExitReason::MaxBars)));
+    }
+}
@@ -1,21 +1,21 @@
 
-use crate::types::{CustomQuote, StockQuote};
+use crate::providers::provider;
+use crate::types::{CustomQuote, StockQuote, Timeframe};
 use crate::STOCK_DATA;
-use chrono::{DateTime, TimeZone, Utc};
+use chrono::{DateTime, NaiveDate, TimeZone, Utc};
 use once_cell::sync::Lazy;
-use reqwest::StatusCode;
 use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, RwLock};
 
 pub static MIN_DATE: Lazy<DateTime<Utc>> =
     Lazy::new(|| Utc.with_ymd_and_hms(2000, 1, 1, 0, 0, 0).unwrap());
-pub static FETCH_FAILED: Lazy<RwLock<HashSet<(String, String)>>> =
+pub static FETCH_FAILED: Lazy<RwLock<HashSet<(String, Timeframe)>>> =
     Lazy::new(|| RwLock::new(HashSet::new()));
-pub static ONGOING_REQUESTS: Lazy<RwLock<HashMap<(String, String), Arc<tokio::sync::RwLock<()>>>>> =
+pub static ONGOING_REQUESTS: Lazy<RwLock<HashMap<(String, Timeframe), Arc<tokio::sync::RwLock<()>>>>> =
     Lazy::new(|| RwLock::new(HashMap::new()));
 
 
-pub async fn fetch_stock_data(symbol: &str, timeframe: &str) -> Result<Vec<StockQuote>, String> {
+pub async fn fetch_stock_data(symbol: &str, timeframe: Timeframe) -> Result<Vec<StockQuote>, String> {
     let (from, to) = {
         let data_map = STOCK_DATA
             .read()
@@ -24,80 +24,57 @@ pub async fn fetch_stock_data(symbol: &str, timeframe: &str) -> Result<Vec<Stock
         let stock_data = stock_model
             .stock_datas
             .get(symbol)
-            .and_then(|tf_map| tf_map.get(timeframe))
+            .and_then(|tf_map| tf_map.get(&timeframe))
             .ok_or("Stock data not found for the given key")?;
         (stock_data.from, stock_data.to)
     };
 
-    let client = reqwest::Client::new();
-    let url = format!(
-        "http://localhost:3000/stock/chart/{}?timeframe={}&period1={}&period2={}",
-        symbol, timeframe, from, to
-    );
-
-    let response = client.get(&url).send().await.map_err(|e| e.to_string())?;
-
-    match response.status() {
-        StatusCode::OK => {
-            let stock_data: Vec<StockQuote> = response.json().await.map_err(|e| e.to_string())?;
-            Ok(stock_data)
-        }
-        status => {
-            Err(format!("Failed to fetch stock data: HTTP {}", status))
-        }
-    }
+    provider().fetch_bars(symbol, timeframe, from, to).await
 }
 
 pub async fn fetch_stock_data_for_backtest(
     symbol: &str,
-    timeframe: &str,
+    timeframe: Timeframe,
     from: &str,
     to: &str
 ) -> Result<Vec<StockQuote>, String> {
-    let client = reqwest::Client::new();
-    let url = format!(
-        "http://localhost:3000/stock/chart/{}?timeframe={}&period1={}&period2={}",
-        symbol, timeframe, from, to
-    );
-
-    let response = client.get(&url).send().await.map_err(|e| e.to_string())?;
+    let from = parse_date(from)?;
+    let to = parse_date(to)?;
 
-    match response.status() {
-        StatusCode::OK => {
-            let stock_data: Vec<StockQuote> = response.json().await.map_err(|e| e.to_string())?;
-            Ok(stock_data)
-        }
-        status => {
-            Err(format!("Failed to fetch stock data: HTTP {}", status))
-        }
+    if let Some(quotes) = covered_stock_data(symbol, timeframe, from, to) {
+        return Ok(quotes);
     }
-
+    provider().fetch_bars(symbol, timeframe, from, to).await
 }
 
-pub fn transform_to_custom_quotes(stock_data: Vec<StockQuote>) -> Vec<CustomQuote> {
-    let mut custom_quotes: Vec<CustomQuote> = Vec::new();
-
-    for (index, quote) in stock_data.iter().enumerate() {
-        let open = if index == 0 {
-            quote.open
-        } else {
-            stock_data[index - 1].close
-        };
-
-        let custom_quote = CustomQuote {
-            high: quote.high,
-            volume: quote.volume,
-            open: open,
-            low: quote.low,
-            close: quote.close,
-            time: quote.date,
-        };
+/// Bars held in `STOCK_DATA` when they cover the whole range, so fetched, cached or
+/// imported history is preferred over the provider. A partly covered range goes to the
+/// provider, and its error is returned rather than backtesting on a partial slice.
+fn covered_stock_data(
+    symbol: &str,
+    timeframe: Timeframe,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> Option<Vec<StockQuote>> {
+    let data_map = STOCK_DATA.read().ok()?;
+    let quotes = data_map.get("stocks")?.covered_data(symbol, timeframe, from, to)?;
+    Some(quotes.iter().map(StockQuote::from).collect())
+}
 
-        custom_quotes.push(custom_quote);
+pub fn parse_date(date: &str) -> Result<DateTime<Utc>, String> {
+    if let Ok(datetime) = DateTime::parse_from_rfc3339(date) {
+        return Ok(datetime.with_timezone(&Utc));
     }
+    NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .map(|day| Utc.from_utc_datetime(&day.and_hms_opt(0, 0, 0).unwrap()))
+        .map_err(|_| format!("Invalid date: {}", date))
+}
 
+/// Converts provider bars as they are, each keeping its own open so gaps between one
+/// bar's close and the next bar's open show up in charts and fills.
+pub fn transform_to_custom_quotes(stock_data: Vec<StockQuote>) -> Vec<CustomQuote> {
+    let mut custom_quotes: Vec<CustomQuote> = stock_data.iter().map(CustomQuote::from).collect();
     custom_quotes.sort_by_key(|quote| quote.time);
-
     custom_quotes
 }
 
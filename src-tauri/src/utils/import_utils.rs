@@ -0,0 +1,430 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use parquet::file::reader::{FileReader, SerializedFileReader};
+use parquet::record::Field;
+use serde::Deserialize;
+
+use crate::types::{CustomQuote, StockQuote};
+
+use super::fetch_stock_utils::filter_complete_quotes;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ColumnMapping {
+    pub date: String,
+    pub open: String,
+    pub high: String,
+    pub low: String,
+    pub close: String,
+    pub volume: Option<String>,
+}
+
+impl Default for ColumnMapping {
+    fn default() -> Self {
+        Self {
+            date: "Date".to_string(),
+            open: "Open".to_string(),
+            high: "High".to_string(),
+            low: "Low".to_string(),
+            close: "Close".to_string(),
+            volume: Some("Volume".to_string()),
+        }
+    }
+}
+
+/// How to read a vendor file. `date_format` is a chrono format string; without it
+/// RFC 3339, `%Y-%m-%d %H:%M:%S`, `%Y-%m-%d` and unix seconds are tried in turn.
+/// `timezone` (IANA name, e.g. `America/New_York`) applies to dates without an offset.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ImportOptions {
+    pub columns: ColumnMapping,
+    pub date_format: Option<String>,
+    pub timezone: Option<String>,
+    pub delimiter: char,
+}
+
+impl Default for ImportOptions {
+    fn default() -> Self {
+        Self {
+            columns: ColumnMapping::default(),
+            date_format: None,
+            timezone: None,
+            delimiter: ',',
+        }
+    }
+}
+
+/// Reads a `.csv` or `.parquet` file into validated, time-sorted quotes.
+pub fn import_file<P: AsRef<Path>>(path: P, options: &ImportOptions) -> Result<Vec<CustomQuote>, String> {
+    let path = path.as_ref();
+    let extension = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(|extension| extension.to_lowercase());
+    let file = File::open(path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+
+    let stock_data = match extension.as_deref() {
+        Some("parquet") => parse_parquet(file, options)?,
+        _ => parse_csv(file, options)?,
+    };
+
+    Ok(validate_quotes(stock_data))
+}
+
+pub fn parse_csv<R: Read>(reader: R, options: &ImportOptions) -> Result<Vec<StockQuote>, String> {
+    if !options.delimiter.is_ascii() {
+        return Err(format!("Delimiter must be an ASCII character: {}", options.delimiter));
+    }
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(options.delimiter as u8)
+        .trim(csv::Trim::All)
+        .from_reader(reader);
+    let headers = reader.headers().map_err(|e| e.to_string())?.clone();
+    let timezone = parse_timezone(options.timezone.as_deref())?;
+
+    let find = |name: &str| -> Result<usize, String> {
+        headers
+            .iter()
+            .position(|header| header.eq_ignore_ascii_case(name))
+            .ok_or_else(|| format!("Column not found: {}", name))
+    };
+    let columns = &options.columns;
+    let date_index = find(&columns.date)?;
+    let open_index = find(&columns.open)?;
+    let high_index = find(&columns.high)?;
+    let low_index = find(&columns.low)?;
+    let close_index = find(&columns.close)?;
+    let volume_index = columns.volume.as_deref().map(find).transpose()?;
+
+    let mut stock_data = Vec::new();
+    for (line, record) in reader.records().enumerate() {
+        let record = record.map_err(|e| e.to_string())?;
+        let field = |index: usize| record.get(index).unwrap_or("");
+        let date = parse_datetime(field(date_index), options.date_format.as_deref(), timezone)
+            .map_err(|e| format!("Row {}: {}", line + 1, e))?;
+
+        stock_data.push(StockQuote {
+            open: parse_price(field(open_index)),
+            high: parse_price(field(high_index)),
+            low: parse_price(field(low_index)),
+            close: parse_price(field(close_index)),
+            volume: volume_index.and_then(|index| parse_volume(field(index))),
+            date,
+        });
+    }
+
+    Ok(stock_data)
+}
+
+pub fn parse_parquet(file: File, options: &ImportOptions) -> Result<Vec<StockQuote>, String> {
+    let reader = SerializedFileReader::new(file).map_err(|e| e.to_string())?;
+    let timezone = parse_timezone(options.timezone.as_deref())?;
+    let columns = &options.columns;
+
+    let names: Vec<String> = reader
+        .metadata()
+        .file_metadata()
+        .schema_descr()
+        .columns()
+        .iter()
+        .map(|column| column.name().to_lowercase())
+        .collect();
+    let required = [&columns.date, &columns.open, &columns.high, &columns.low, &columns.close];
+    for name in required.into_iter().chain(columns.volume.as_ref()) {
+        if !names.contains(&name.to_lowercase()) {
+            return Err(format!("Column not found: {}", name));
+        }
+    }
+
+    let mut stock_data = Vec::new();
+    let rows = reader.get_row_iter(None).map_err(|e| e.to_string())?;
+    for (line, row) in rows.enumerate() {
+        let row = row.map_err(|e| e.to_string())?;
+        let fields: HashMap<String, &Field> = row
+            .get_column_iter()
+            .map(|(name, field)| (name.to_lowercase(), field))
+            .collect();
+        let get = |name: &str| fields.get(&name.to_lowercase()).copied();
+
+        let date = match get(&columns.date) {
+            Some(field) => field_to_datetime(field, options.date_format.as_deref(), timezone),
+            None => Err(format!("Column not found: {}", columns.date)),
+        }
+        .map_err(|e| format!("Row {}: {}", line + 1, e))?;
+
+        stock_data.push(StockQuote {
+            open: get(&columns.open).and_then(field_to_f64),
+            high: get(&columns.high).and_then(field_to_f64),
+            low: get(&columns.low).and_then(field_to_f64),
+            close: get(&columns.close).and_then(field_to_f64),
+            volume: columns
+                .volume
+                .as_deref()
+                .and_then(get)
+                .and_then(field_to_f64)
+                .filter(|volume| *volume >= 0.0)
+                .map(|volume| volume as u64),
+            date,
+        });
+    }
+
+    Ok(stock_data)
+}
+
+/// Drops incomplete bars like data from the provider, plus sanity checks vendor files
+/// need: bars with inverted high/low or non-finite prices are dropped and duplicate times
+/// keep the last row. The file's OHLC are kept as they are.
+pub fn validate_quotes(stock_data: Vec<StockQuote>) -> Vec<CustomQuote> {
+    let mut by_time: HashMap<DateTime<Utc>, StockQuote> = HashMap::new();
+    for quote in filter_complete_quotes(stock_data) {
+        let prices = [quote.open, quote.high, quote.low, quote.close].map(|price| price.unwrap_or(f64::NAN));
+        if prices.iter().any(|price| !price.is_finite()) || prices[1] < prices[2] {
+            continue;
+        }
+        by_time.insert(quote.date, quote);
+    }
+
+    let mut deduplicated: Vec<StockQuote> = by_time.into_values().collect();
+    deduplicated.sort_by_key(|quote| quote.date);
+    deduplicated.iter().map(CustomQuote::from).collect()
+}
+
+fn parse_timezone(timezone: Option<&str>) -> Result<Tz, String> {
+    match timezone {
+        Some(name) => name.parse::<Tz>().map_err(|_| format!("Invalid timezone: {}", name)),
+        None => Ok(Tz::UTC),
+    }
+}
+
+fn parse_datetime(value: &str, format: Option<&str>, timezone: Tz) -> Result<DateTime<Utc>, String> {
+    let value = value.trim();
+    if let Some(format) = format {
+        if let Ok(datetime) = DateTime::parse_from_str(value, format) {
+            return Ok(datetime.with_timezone(&Utc));
+        }
+        if let Ok(datetime) = NaiveDateTime::parse_from_str(value, format) {
+            return localize(datetime, timezone);
+        }
+        if let Ok(date) = NaiveDate::parse_from_str(value, format) {
+            return localize(date.and_hms_opt(0, 0, 0).unwrap(), timezone);
+        }
+        return Err(format!("Date {} does not match format {}", value, format));
+    }
+
+    if let Ok(datetime) = DateTime::parse_from_rfc3339(value) {
+        return Ok(datetime.with_timezone(&Utc));
+    }
+    for format in ["%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M"] {
+        if let Ok(datetime) = NaiveDateTime::parse_from_str(value, format) {
+            return localize(datetime, timezone);
+        }
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return localize(date.and_hms_opt(0, 0, 0).unwrap(), timezone);
+    }
+    if let Ok(seconds) = value.parse::<i64>() {
+        return Utc
+            .timestamp_opt(seconds, 0)
+            .single()
+            .ok_or_else(|| format!("Invalid timestamp: {}", value));
+    }
+    Err(format!("Unrecognized date: {}", value))
+}
+
+fn localize(datetime: NaiveDateTime, timezone: Tz) -> Result<DateTime<Utc>, String> {
+    timezone
+        .from_local_datetime(&datetime)
+        .earliest()
+        .map(|local| local.with_timezone(&Utc))
+        .ok_or_else(|| format!("Nonexistent local time: {}", datetime))
+}
+
+fn field_to_datetime(field: &Field, format: Option<&str>, timezone: Tz) -> Result<DateTime<Utc>, String> {
+    match field {
+        Field::Str(value) => parse_datetime(value, format, timezone),
+        Field::Date(days) => localize(
+            NaiveDate::from_ymd_opt(1970, 1, 1).unwrap().and_hms_opt(0, 0, 0).unwrap()
+                + Duration::days(*days as i64),
+            timezone,
+        ),
+        Field::TimestampMillis(millis) => Utc
+            .timestamp_millis_opt(*millis)
+            .single()
+            .ok_or_else(|| format!("Invalid timestamp: {}", millis)),
+        Field::TimestampMicros(micros) => Utc
+            .timestamp_millis_opt(micros / 1000)
+            .single()
+            .ok_or_else(|| format!("Invalid timestamp: {}", micros)),
+        Field::Long(seconds) => parse_datetime(&seconds.to_string(), None, timezone),
+        Field::Int(seconds) => parse_datetime(&seconds.to_string(), None, timezone),
+        other => Err(format!("Unsupported date value: {}", other)),
+    }
+}
+
+fn field_to_f64(field: &Field) -> Option<f64> {
+    match field {
+        Field::Double(value) => Some(*value),
+        Field::Float(value) => Some(*value as f64),
+        Field::Long(value) => Some(*value as f64),
+        Field::Int(value) => Some(*value as f64),
+        Field::ULong(value) => Some(*value as f64),
+        Field::UInt(value) => Some(*value as f64),
+        Field::Str(value) => parse_price(value),
+        _ => None,
+    }
+}
+
+fn parse_price(value: &str) -> Option<f64> {
+    let value = value.trim().replace(',', "");
+    if value.is_empty() {
+        return None;
+    }
+    value.parse::<f64>().ok()
+}
+
+fn parse_volume(value: &str) -> Option<u64> {
+    parse_price(value)
+        .filter(|volume| *volume >= 0.0)
+        .map(|volume| volume as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use std::sync::Arc;
+
+    use parquet::data_type::{ByteArray, ByteArrayType, DoubleType};
+    use parquet::file::properties::WriterProperties;
+    use parquet::file::writer::SerializedFileWriter;
+    use parquet::schema::parser::parse_message_type;
+
+    /// Writes a `Date` string column followed by the given double columns to a temp file.
+    fn write_parquet(name: &str, dates: &[&str], columns: &[(&str, &[f64])]) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("{}-{}.parquet", name, std::process::id()));
+        let fields: String = columns
+            .iter()
+            .map(|(column, _)| format!(" REQUIRED DOUBLE {};", column))
+            .collect();
+        let schema = parse_message_type(&format!("message bars {{ REQUIRED BYTE_ARRAY Date (UTF8);{} }}", fields)).unwrap();
+        let properties = Arc::new(WriterProperties::builder().build());
+        let mut writer = SerializedFileWriter::new(File::create(&path).unwrap(), Arc::new(schema), properties).unwrap();
+
+        let mut row_group = writer.next_row_group().unwrap();
+        let mut date_column = row_group.next_column().unwrap().unwrap();
+        let dates: Vec<ByteArray> = dates.iter().map(|date| ByteArray::from(*date)).collect();
+        date_column.typed::<ByteArrayType>().write_batch(&dates, None, None).unwrap();
+        date_column.close().unwrap();
+        for (_, values) in columns {
+            let mut column = row_group.next_column().unwrap().unwrap();
+            column.typed::<DoubleType>().write_batch(values, None, None).unwrap();
+            column.close().unwrap();
+        }
+        row_group.close().unwrap();
+        writer.close().unwrap();
+        path
+    }
+
+    #[test]
+    fn test_parse_csv_with_mapping_and_timezone() {
+        let csv = "time;o;h;l;c;vol\n\
+                   02/01/2023 09:30;10;12;9;11;1000\n\
+                   03/01/2023 09:30;11;13;10;12;\n";
+        let options = ImportOptions {
+            columns: ColumnMapping {
+                date: "time".to_string(),
+                open: "o".to_string(),
+                high: "h".to_string(),
+                low: "l".to_string(),
+                close: "c".to_string(),
+                volume: Some("vol".to_string()),
+            },
+            date_format: Some("%d/%m/%Y %H:%M".to_string()),
+            timezone: Some("America/New_York".to_string()),
+            delimiter: ';',
+        };
+
+        let quotes = parse_csv(csv.as_bytes(), &options).unwrap();
+        assert_eq!(quotes.len(), 2);
+        assert_eq!(quotes[0].date, Utc.with_ymd_and_hms(2023, 1, 2, 14, 30, 0).unwrap());
+        assert_eq!(quotes[0].volume, Some(1000));
+        assert_eq!(quotes[1].volume, None);
+        assert_eq!(quotes[1].close, Some(12.0));
+    }
+
+    #[test]
+    fn test_validate_quotes_drops_bad_rows() {
+        let csv = "Date,Open,High,Low,Close,Volume\n\
+                   2023-01-04,11,13,10,12,100\n\
+                   2023-01-03,10,12,9,11,100\n\
+                   2023-01-05,11,9,13,12,100\n\
+                   2023-01-06,11,,10,12,100\n\
+                   2023-01-03,10,12,9,10.5,100\n";
+        let stock_data = parse_csv(csv.as_bytes(), &ImportOptions::default()).unwrap();
+        let quotes = validate_quotes(stock_data);
+
+        assert_eq!(quotes.len(), 2);
+        assert_eq!(quotes[0].time, Utc.with_ymd_and_hms(2023, 1, 3, 0, 0, 0).unwrap());
+        assert_eq!(quotes[0].close, Some(10.5));
+        assert_eq!(quotes[1].open, Some(11.0));
+    }
+
+    #[test]
+    fn test_missing_column_is_an_error() {
+        let csv = "Date,Open,High,Low\n2023-01-03,10,12,9\n";
+        let result = parse_csv(csv.as_bytes(), &ImportOptions::default());
+        assert_eq!(result.unwrap_err(), "Column not found: Close");
+    }
+
+    #[test]
+    fn test_parquet_round_trip() {
+        let path = write_parquet(
+            "import-round-trip",
+            &["2023-01-04", "2023-01-03"],
+            &[
+                ("Open", &[11.0, 10.0]),
+                ("High", &[13.0, 12.0]),
+                ("Low", &[10.0, 9.0]),
+                ("Close", &[12.0, 10.5]),
+                ("Volume", &[200.0, 100.0]),
+            ],
+        );
+        let quotes = import_file(&path, &ImportOptions::default());
+        std::fs::remove_file(&path).unwrap();
+
+        let quotes = quotes.unwrap();
+        assert_eq!(quotes.len(), 2);
+        assert_eq!(quotes[0].time, Utc.with_ymd_and_hms(2023, 1, 3, 0, 0, 0).unwrap());
+        assert_eq!(quotes[0].close, Some(10.5));
+        assert_eq!(quotes[1].open, Some(11.0));
+        assert_eq!(quotes[1].volume, Some(200));
+    }
+
+    #[test]
+    fn test_parquet_missing_column_is_an_error() {
+        let path = write_parquet(
+            "import-missing-column",
+            &["2023-01-03"],
+            &[("Open", &[10.0]), ("High", &[12.0]), ("Low", &[9.0]), ("Volume", &[100.0])],
+        );
+        let result = parse_parquet(File::open(&path).unwrap(), &ImportOptions::default());
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(result.unwrap_err(), "Column not found: Close");
+    }
+
+    #[test]
+    fn test_non_ascii_delimiter_is_an_error() {
+        let options = ImportOptions {
+            delimiter: '§',
+            ..Default::default()
+        };
+        let result = parse_csv("Date§Open\n".as_bytes(), &options);
+        assert_eq!(result.unwrap_err(), "Delimiter must be an ASCII character: §");
+    }
+}
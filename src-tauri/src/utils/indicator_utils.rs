@@ -1,4 +1,7 @@
-use crate::types::{CustomQuote, IndicatorData};
+use chrono::{DateTime, Datelike, Utc};
+use serde::Serialize;
+
+use crate::types::{CustomQuote, IndicatorData, Timeframe};
 
 pub fn calculate_sma(data: &[CustomQuote], period: usize) -> Vec<IndicatorData> {
     let mut sma_data = Vec::new();
@@ -124,3 +127,1071 @@ pub fn calculate_volume(data: &[CustomQuote]) -> Vec<IndicatorData> {
 
     volume_data
 }
+
+/// True range of each bar; the first bar has no previous close and uses its high-low range.
+pub fn calculate_true_range(data: &[CustomQuote]) -> Vec<f64> {
+    data.iter()
+        .enumerate()
+        .map(|(i, quote)| {
+            let close = quote.close.unwrap_or(0.0);
+            let high = quote.high.unwrap_or(close);
+            let low = quote.low.unwrap_or(close);
+            match i.checked_sub(1).and_then(|previous| data[previous].close) {
+                Some(previous_close) => (high - low)
+                    .max((high - previous_close).abs())
+                    .max((low - previous_close).abs()),
+                None => high - low,
+            }
+        })
+        .collect()
+}
+
+/// Wilder's smoothing, seeded by the simple average of the first `period` values
+/// (earlier values average what is available, like `calculate_sma`).
+fn wilder_smooth(values: &[f64], period: usize) -> Vec<f64> {
+    let period = period.max(1);
+    let mut smoothed = Vec::with_capacity(values.len());
+    let mut average = 0.0;
+
+    for (i, value) in values.iter().enumerate() {
+        average = if i < period {
+            (average * i as f64 + value) / (i + 1) as f64
+        } else {
+            (average * (period - 1) as f64 + value) / period as f64
+        };
+        smoothed.push(average);
+    }
+
+    smoothed
+}
+
+/// Average true range with Wilder's smoothing.
+pub fn calculate_atr(data: &[CustomQuote], period: usize) -> Vec<IndicatorData> {
+    wilder_smooth(&calculate_true_range(data), period)
+        .into_iter()
+        .zip(data)
+        .map(|(value, quote)| IndicatorData { time: quote.time, value })
+        .collect()
+}
+
+/// Upper, middle and lower lines of a price envelope.
+pub struct Channel {
+    pub upper: Vec<IndicatorData>,
+    pub middle: Vec<IndicatorData>,
+    pub lower: Vec<IndicatorData>,
+}
+
+/// Start of the trailing window ending at `i`; early bars use what is available.
+fn window_start(i: usize, period: usize) -> usize {
+    (i + 1).saturating_sub(period.max(1))
+}
+
+/// SMA of the close with bands `multiplier` population standard deviations away.
+pub fn calculate_bollinger_bands(data: &[CustomQuote], period: usize, multiplier: f64) -> Channel {
+    let middle = calculate_sma(data, period.max(1));
+    let mut upper = Vec::with_capacity(data.len());
+    let mut lower = Vec::with_capacity(data.len());
+
+    for (i, mean) in middle.iter().enumerate() {
+        let window = &data[window_start(i, period)..=i];
+        let variance = window
+            .iter()
+            .map(|quote| (quote.close.unwrap_or(0.0) - mean.value).powi(2))
+            .sum::<f64>()
+            / window.len() as f64;
+        let offset = multiplier * variance.sqrt();
+        upper.push(IndicatorData { time: mean.time, value: mean.value + offset });
+        lower.push(IndicatorData { time: mean.time, value: mean.value - offset });
+    }
+
+    Channel { upper, middle, lower }
+}
+
+/// Position of the close inside the bands: 0 at the lower band, 1 at the upper band.
+pub fn calculate_percent_b(data: &[CustomQuote], bands: &Channel) -> Vec<IndicatorData> {
+    data.iter()
+        .zip(bands.upper.iter().zip(&bands.lower))
+        .map(|(quote, (upper, lower))| {
+            let width = upper.value - lower.value;
+            let value = if width == 0.0 {
+                0.5
+            } else {
+                (quote.close.unwrap_or(0.0) - lower.value) / width
+            };
+            IndicatorData { time: quote.time, value }
+        })
+        .collect()
+}
+
+/// Band width relative to the middle line.
+pub fn calculate_bandwidth(bands: &Channel) -> Vec<IndicatorData> {
+    bands
+        .middle
+        .iter()
+        .zip(bands.upper.iter().zip(&bands.lower))
+        .map(|(middle, (upper, lower))| {
+            let value = if middle.value == 0.0 {
+                0.0
+            } else {
+                (upper.value - lower.value) / middle.value
+            };
+            IndicatorData { time: middle.time, value }
+        })
+        .collect()
+}
+
+/// EMA of the close with bands `multiplier` ATRs away.
+pub fn calculate_keltner_channels(
+    data: &[CustomQuote],
+    period: usize,
+    atr_period: usize,
+    multiplier: f64,
+) -> Channel {
+    let middle = calculate_ema(data, period);
+    let atr = calculate_atr(data, atr_period);
+    let upper = middle
+        .iter()
+        .zip(&atr)
+        .map(|(mean, atr)| IndicatorData { time: mean.time, value: mean.value + multiplier * atr.value })
+        .collect();
+    let lower = middle
+        .iter()
+        .zip(&atr)
+        .map(|(mean, atr)| IndicatorData { time: mean.time, value: mean.value - multiplier * atr.value })
+        .collect();
+
+    Channel { upper, middle, lower }
+}
+
+/// Highest high and lowest low over the trailing `period` bars, and their midpoint.
+pub fn calculate_donchian_channels(data: &[CustomQuote], period: usize) -> Channel {
+    let mut channel = Channel {
+        upper: Vec::with_capacity(data.len()),
+        middle: Vec::with_capacity(data.len()),
+        lower: Vec::with_capacity(data.len()),
+    };
+
+    for (i, quote) in data.iter().enumerate() {
+        let (high, low) = window_range(data, i, period);
+        channel.upper.push(IndicatorData { time: quote.time, value: high });
+        channel.middle.push(IndicatorData { time: quote.time, value: (high + low) / 2.0 });
+        channel.lower.push(IndicatorData { time: quote.time, value: low });
+    }
+
+    channel
+}
+
+/// Simple moving average of a derived series; early values average what is available.
+pub fn calculate_sma_for_indicator_data(data: &[IndicatorData], period: usize) -> Vec<IndicatorData> {
+    let period = period.max(1);
+    let mut sma_data = Vec::with_capacity(data.len());
+    let mut sum = 0.0;
+
+    for (i, point) in data.iter().enumerate() {
+        sum += point.value;
+        if i >= period {
+            sum -= data[i - period].value;
+        }
+        let count = (i + 1).min(period);
+        sma_data.push(IndicatorData { time: point.time, value: sum / count as f64 });
+    }
+
+    sma_data
+}
+
+fn high_of(quote: &CustomQuote) -> f64 {
+    quote.high.or(quote.close).unwrap_or(0.0)
+}
+
+fn low_of(quote: &CustomQuote) -> f64 {
+    quote.low.or(quote.close).unwrap_or(0.0)
+}
+
+fn typical_price(quote: &CustomQuote) -> f64 {
+    (high_of(quote) + low_of(quote) + quote.close.unwrap_or(0.0)) / 3.0
+}
+
+/// Highest high and lowest low of the trailing window ending at `i`.
+fn window_range(data: &[CustomQuote], i: usize, period: usize) -> (f64, f64) {
+    data[window_start(i, period)..=i]
+        .iter()
+        .fold((f64::MIN, f64::MAX), |(high, low), quote| {
+            (high.max(high_of(quote)), low.min(low_of(quote)))
+        })
+}
+
+/// Stochastic oscillator. `k_smoothing` of 1 gives the fast stochastic, 3 the slow one;
+/// any other combination is the full stochastic. Returns (%K, %D).
+pub fn calculate_stochastic(
+    data: &[CustomQuote],
+    k_period: usize,
+    k_smoothing: usize,
+    d_period: usize,
+) -> (Vec<IndicatorData>, Vec<IndicatorData>) {
+    let raw_k: Vec<IndicatorData> = data
+        .iter()
+        .enumerate()
+        .map(|(i, quote)| {
+            let (high, low) = window_range(data, i, k_period);
+            let value = if high == low {
+                50.0
+            } else {
+                100.0 * (quote.close.unwrap_or(0.0) - low) / (high - low)
+            };
+            IndicatorData { time: quote.time, value }
+        })
+        .collect();
+
+    let k = calculate_sma_for_indicator_data(&raw_k, k_smoothing);
+    let d = calculate_sma_for_indicator_data(&k, d_period);
+    (k, d)
+}
+
+/// Williams %R: where the close sits in the trailing range, from -100 (low) to 0 (high).
+pub fn calculate_williams_r(data: &[CustomQuote], period: usize) -> Vec<IndicatorData> {
+    data.iter()
+        .enumerate()
+        .map(|(i, quote)| {
+            let (high, low) = window_range(data, i, period);
+            let value = if high == low {
+                -50.0
+            } else {
+                -100.0 * (high - quote.close.unwrap_or(0.0)) / (high - low)
+            };
+            IndicatorData { time: quote.time, value }
+        })
+        .collect()
+}
+
+/// Commodity Channel Index of the typical price, scaled by Lambert's 0.015 constant.
+pub fn calculate_cci(data: &[CustomQuote], period: usize) -> Vec<IndicatorData> {
+    let typical: Vec<f64> = data.iter().map(typical_price).collect();
+
+    data.iter()
+        .enumerate()
+        .map(|(i, quote)| {
+            let window = &typical[window_start(i, period)..=i];
+            let mean = window.iter().sum::<f64>() / window.len() as f64;
+            let deviation = window.iter().map(|value| (value - mean).abs()).sum::<f64>() / window.len() as f64;
+            let value = if deviation == 0.0 {
+                0.0
+            } else {
+                (typical[i] - mean) / (0.015 * deviation)
+            };
+            IndicatorData { time: quote.time, value }
+        })
+        .collect()
+}
+
+/// Percentage change of the close over `period` bars; starts at index `period` like RSI.
+pub fn calculate_roc(data: &[CustomQuote], period: usize) -> Vec<IndicatorData> {
+    (period.max(1)..data.len())
+        .map(|i| {
+            let previous = data[i - period.max(1)].close.unwrap_or(0.0);
+            let value = if previous == 0.0 {
+                0.0
+            } else {
+                100.0 * (data[i].close.unwrap_or(0.0) - previous) / previous
+            };
+            IndicatorData { time: data[i].time, value }
+        })
+        .collect()
+}
+
+/// Money Flow Index: a volume-weighted RSI of the typical price; starts at index `period`.
+pub fn calculate_mfi(data: &[CustomQuote], period: usize) -> Vec<IndicatorData> {
+    let period = period.max(1);
+    let typical: Vec<f64> = data.iter().map(typical_price).collect();
+
+    (period..data.len())
+        .map(|i| {
+            let (mut positive, mut negative) = (0.0, 0.0);
+            for j in (i - period + 1)..=i {
+                let flow = typical[j] * data[j].volume.unwrap_or(0) as f64;
+                if typical[j] > typical[j - 1] {
+                    positive += flow;
+                } else if typical[j] < typical[j - 1] {
+                    negative += flow;
+                }
+            }
+            let value = if negative == 0.0 {
+                100.0
+            } else {
+                100.0 - 100.0 / (1.0 + positive / negative)
+            };
+            IndicatorData { time: data[i].time, value }
+        })
+        .collect()
+}
+
+/// Ultimate Oscillator: buying pressure over true range across three windows, weighted 4:2:1.
+pub fn calculate_ultimate_oscillator(
+    data: &[CustomQuote],
+    short_period: usize,
+    medium_period: usize,
+    long_period: usize,
+) -> Vec<IndicatorData> {
+    let true_ranges = calculate_true_range(data);
+    let buying_pressure: Vec<f64> = data
+        .iter()
+        .enumerate()
+        .map(|(i, quote)| {
+            let close = quote.close.unwrap_or(0.0);
+            let floor = match i.checked_sub(1).and_then(|previous| data[previous].close) {
+                Some(previous_close) => low_of(quote).min(previous_close),
+                None => low_of(quote),
+            };
+            close - floor
+        })
+        .collect();
+
+    let average = |i: usize, period: usize| {
+        let start = window_start(i, period);
+        let range: f64 = true_ranges[start..=i].iter().sum();
+        if range == 0.0 {
+            0.5
+        } else {
+            buying_pressure[start..=i].iter().sum::<f64>() / range
+        }
+    };
+
+    data.iter()
+        .enumerate()
+        .map(|(i, quote)| {
+            let value = 100.0
+                * (4.0 * average(i, short_period) + 2.0 * average(i, medium_period) + average(i, long_period))
+                / 7.0;
+            IndicatorData { time: quote.time, value }
+        })
+        .collect()
+}
+
+/// Wilder's directional movement system.
+pub struct Dmi {
+    pub adx: Vec<IndicatorData>,
+    pub plus_di: Vec<IndicatorData>,
+    pub minus_di: Vec<IndicatorData>,
+}
+
+/// ADX with +DI/-DI; directional movement, true range and DX all use Wilder's smoothing.
+pub fn calculate_dmi(data: &[CustomQuote], period: usize) -> Dmi {
+    let mut plus_dm = vec![0.0; data.len()];
+    let mut minus_dm = vec![0.0; data.len()];
+    for i in 1..data.len() {
+        let up_move = high_of(&data[i]) - high_of(&data[i - 1]);
+        let down_move = low_of(&data[i - 1]) - low_of(&data[i]);
+        if up_move > down_move && up_move > 0.0 {
+            plus_dm[i] = up_move;
+        }
+        if down_move > up_move && down_move > 0.0 {
+            minus_dm[i] = down_move;
+        }
+    }
+
+    let true_range = wilder_smooth(&calculate_true_range(data), period);
+    let plus_dm = wilder_smooth(&plus_dm, period);
+    let minus_dm = wilder_smooth(&minus_dm, period);
+    let directional_index = |dm: f64, tr: f64| if tr == 0.0 { 0.0 } else { 100.0 * dm / tr };
+
+    let mut dmi = Dmi {
+        adx: Vec::with_capacity(data.len()),
+        plus_di: Vec::with_capacity(data.len()),
+        minus_di: Vec::with_capacity(data.len()),
+    };
+    let mut dx = Vec::with_capacity(data.len());
+    for (i, quote) in data.iter().enumerate() {
+        let plus = directional_index(plus_dm[i], true_range[i]);
+        let minus = directional_index(minus_dm[i], true_range[i]);
+        dx.push(if plus + minus == 0.0 { 0.0 } else { 100.0 * (plus - minus).abs() / (plus + minus) });
+        dmi.plus_di.push(IndicatorData { time: quote.time, value: plus });
+        dmi.minus_di.push(IndicatorData { time: quote.time, value: minus });
+    }
+    for (value, quote) in wilder_smooth(&dx, period).into_iter().zip(data) {
+        dmi.adx.push(IndicatorData { time: quote.time, value });
+    }
+
+    dmi
+}
+
+/// Wilder's Parabolic SAR. The first bar seeds the trend from the direction of the second.
+pub fn calculate_parabolic_sar(data: &[CustomQuote], step: f64, max_step: f64) -> Vec<IndicatorData> {
+    let Some(first) = data.first() else {
+        return Vec::new();
+    };
+    let mut rising = data.get(1).is_none_or(|second| second.close >= first.close);
+    let mut sar = if rising { low_of(first) } else { high_of(first) };
+    let mut extreme = if rising { high_of(first) } else { low_of(first) };
+    let mut acceleration = step;
+    let mut sar_data = vec![IndicatorData { time: first.time, value: sar }];
+
+    for i in 1..data.len() {
+        let (high, low) = (high_of(&data[i]), low_of(&data[i]));
+        sar += acceleration * (extreme - sar);
+        let previous = &data[i.saturating_sub(2)..i];
+        if rising {
+            sar = previous.iter().map(low_of).fold(sar, f64::min);
+            if low < sar {
+                rising = false;
+                sar = extreme;
+                extreme = low;
+                acceleration = step;
+            } else if high > extreme {
+                extreme = high;
+                acceleration = (acceleration + step).min(max_step);
+            }
+        } else {
+            sar = previous.iter().map(high_of).fold(sar, f64::max);
+            if high > sar {
+                rising = true;
+                sar = extreme;
+                extreme = high;
+                acceleration = step;
+            } else if low < extreme {
+                extreme = low;
+                acceleration = (acceleration + step).min(max_step);
+            }
+        }
+        sar_data.push(IndicatorData { time: data[i].time, value: sar });
+    }
+
+    sar_data
+}
+
+/// Supertrend: the lower ATR band while the close holds above it, the upper band otherwise.
+/// The close is above the line exactly when the trend is up.
+pub fn calculate_supertrend(data: &[CustomQuote], period: usize, multiplier: f64) -> Vec<IndicatorData> {
+    let atr = calculate_atr(data, period);
+    let mut supertrend = Vec::with_capacity(data.len());
+    let (mut upper, mut lower) = (f64::MAX, f64::MIN);
+    let mut rising = true;
+
+    for (i, quote) in data.iter().enumerate() {
+        let middle = (high_of(quote) + low_of(quote)) / 2.0;
+        let basic_upper = middle + multiplier * atr[i].value;
+        let basic_lower = middle - multiplier * atr[i].value;
+        let previous_close = i.checked_sub(1).and_then(|previous| data[previous].close);
+
+        upper = match previous_close {
+            Some(close) if basic_upper >= upper && close <= upper => upper,
+            _ => basic_upper,
+        };
+        lower = match previous_close {
+            Some(close) if basic_lower <= lower && close >= lower => lower,
+            _ => basic_lower,
+        };
+
+        let close = quote.close.unwrap_or(0.0);
+        if rising && close < lower {
+            rising = false;
+        } else if !rising && close > upper {
+            rising = true;
+        }
+        supertrend.push(IndicatorData {
+            time: quote.time,
+            value: if rising { lower } else { upper },
+        });
+    }
+
+    supertrend
+}
+
+/// Aroon up, down and oscillator: how recently the highest high and lowest low of the last
+/// `period + 1` bars occurred, from 100 (this bar) down to 0 (`period` bars ago).
+pub fn calculate_aroon(
+    data: &[CustomQuote],
+    period: usize,
+) -> (Vec<IndicatorData>, Vec<IndicatorData>, Vec<IndicatorData>) {
+    let period = period.max(1);
+    let mut up = Vec::with_capacity(data.len());
+    let mut down = Vec::with_capacity(data.len());
+    let mut oscillator = Vec::with_capacity(data.len());
+
+    for (i, quote) in data.iter().enumerate() {
+        let start = window_start(i, period + 1);
+        let (mut highest, mut lowest) = (start, start);
+        for j in start..=i {
+            if high_of(&data[j]) >= high_of(&data[highest]) {
+                highest = j;
+            }
+            if low_of(&data[j]) <= low_of(&data[lowest]) {
+                lowest = j;
+            }
+        }
+        let aroon_up = 100.0 * (period - (i - highest)) as f64 / period as f64;
+        let aroon_down = 100.0 * (period - (i - lowest)) as f64 / period as f64;
+        up.push(IndicatorData { time: quote.time, value: aroon_up });
+        down.push(IndicatorData { time: quote.time, value: aroon_down });
+        oscillator.push(IndicatorData { time: quote.time, value: aroon_up - aroon_down });
+    }
+
+    (up, down, oscillator)
+}
+
+/// Vortex indicator (VI+, VI-); starts at index `period` like RSI.
+pub fn calculate_vortex(data: &[CustomQuote], period: usize) -> (Vec<IndicatorData>, Vec<IndicatorData>) {
+    let period = period.max(1);
+    let true_ranges = calculate_true_range(data);
+    let mut plus = Vec::new();
+    let mut minus = Vec::new();
+
+    for i in period..data.len() {
+        let (mut plus_movement, mut minus_movement, mut range) = (0.0, 0.0, 0.0);
+        for j in (i - period + 1)..=i {
+            plus_movement += (high_of(&data[j]) - low_of(&data[j - 1])).abs();
+            minus_movement += (low_of(&data[j]) - high_of(&data[j - 1])).abs();
+            range += true_ranges[j];
+        }
+        let (vi_plus, vi_minus) = if range == 0.0 {
+            (1.0, 1.0)
+        } else {
+            (plus_movement / range, minus_movement / range)
+        };
+        plus.push(IndicatorData { time: data[i].time, value: vi_plus });
+        minus.push(IndicatorData { time: data[i].time, value: vi_minus });
+    }
+
+    (plus, minus)
+}
+
+/// Ichimoku lines before displacement; the chart shifts the spans forward and Chikou back.
+pub struct Ichimoku {
+    pub tenkan: Vec<IndicatorData>,
+    pub kijun: Vec<IndicatorData>,
+    pub senkou_a: Vec<IndicatorData>,
+    pub senkou_b: Vec<IndicatorData>,
+    pub chikou: Vec<IndicatorData>,
+}
+
+pub fn calculate_ichimoku(
+    data: &[CustomQuote],
+    tenkan_period: usize,
+    kijun_period: usize,
+    senkou_b_period: usize,
+) -> Ichimoku {
+    let tenkan = calculate_donchian_channels(data, tenkan_period).middle;
+    let kijun = calculate_donchian_channels(data, kijun_period).middle;
+    let senkou_a = tenkan
+        .iter()
+        .zip(&kijun)
+        .map(|(tenkan, kijun)| IndicatorData { time: tenkan.time, value: (tenkan.value + kijun.value) / 2.0 })
+        .collect();
+    let senkou_b = calculate_donchian_channels(data, senkou_b_period).middle;
+    let chikou = data
+        .iter()
+        .map(|quote| IndicatorData { time: quote.time, value: quote.close.unwrap_or(0.0) })
+        .collect();
+
+    Ichimoku { tenkan, kijun, senkou_a, senkou_b, chikou }
+}
+
+/// Moves each point `offset` bars along the bar timeline of `data`. Points shifted past the
+/// last bar get times projected with `timeframe`; points shifted before the first bar are dropped.
+pub fn shift_series(
+    series: &[IndicatorData],
+    data: &[CustomQuote],
+    offset: i64,
+    timeframe: Timeframe,
+) -> Vec<IndicatorData> {
+    let mut timeline: Vec<_> = data.iter().map(|quote| quote.time).collect();
+    if let Some(&last) = timeline.last() {
+        let mut time = last;
+        for _ in 0..offset.max(0) {
+            time = timeframe.next_bar_time(time);
+            timeline.push(time);
+        }
+    }
+
+    series
+        .iter()
+        .filter_map(|point| {
+            let index = data.binary_search_by_key(&point.time, |quote| quote.time).ok()? as i64;
+            let shifted = usize::try_from(index + offset).ok()?;
+            timeline.get(shifted).map(|&time| IndicatorData { time, value: point.value })
+        })
+        .collect()
+}
+
+fn volume_of(quote: &CustomQuote) -> f64 {
+    quote.volume.unwrap_or(0) as f64
+}
+
+/// On-balance volume: cumulative volume added on up closes and subtracted on down closes.
+pub fn calculate_obv(data: &[CustomQuote]) -> Vec<IndicatorData> {
+    let mut obv = 0.0;
+    data.iter()
+        .enumerate()
+        .map(|(i, quote)| {
+            if let Some(previous) = i.checked_sub(1).map(|previous| &data[previous]) {
+                if quote.close > previous.close {
+                    obv += volume_of(quote);
+                } else if quote.close < previous.close {
+                    obv -= volume_of(quote);
+                }
+            }
+            IndicatorData { time: quote.time, value: obv }
+        })
+        .collect()
+}
+
+/// Close location value times volume; zero for bars without a range.
+fn money_flow_volume(quote: &CustomQuote) -> f64 {
+    let (high, low) = (high_of(quote), low_of(quote));
+    if high == low {
+        return 0.0;
+    }
+    let close = quote.close.unwrap_or(0.0);
+    ((close - low) - (high - close)) / (high - low) * volume_of(quote)
+}
+
+/// Accumulation/distribution line: cumulative money flow volume.
+pub fn calculate_accumulation_distribution(data: &[CustomQuote]) -> Vec<IndicatorData> {
+    let mut line = 0.0;
+    data.iter()
+        .map(|quote| {
+            line += money_flow_volume(quote);
+            IndicatorData { time: quote.time, value: line }
+        })
+        .collect()
+}
+
+/// Chaikin Money Flow: money flow volume over volume across the trailing `period` bars.
+pub fn calculate_chaikin_money_flow(data: &[CustomQuote], period: usize) -> Vec<IndicatorData> {
+    data.iter()
+        .enumerate()
+        .map(|(i, quote)| {
+            let window = &data[window_start(i, period)..=i];
+            let volume: f64 = window.iter().map(volume_of).sum();
+            let value = if volume == 0.0 {
+                0.0
+            } else {
+                window.iter().map(money_flow_volume).sum::<f64>() / volume
+            };
+            IndicatorData { time: quote.time, value }
+        })
+        .collect()
+}
+
+/// Chaikin oscillator: fast minus slow EMA of the accumulation/distribution line.
+pub fn calculate_chaikin_oscillator(data: &[CustomQuote], fast_period: usize, slow_period: usize) -> Vec<IndicatorData> {
+    let line = calculate_accumulation_distribution(data);
+    if line.is_empty() {
+        return line;
+    }
+    let fast = calculate_ema_for_indicator_data(&line, fast_period);
+    let slow = calculate_ema_for_indicator_data(&line, slow_period);
+    fast.iter()
+        .zip(&slow)
+        .map(|(fast, slow)| IndicatorData { time: fast.time, value: fast.value - slow.value })
+        .collect()
+}
+
+/// Where a VWAP starts accumulating. Sessions are UTC calendar days.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VwapAnchor {
+    Session,
+    Week,
+    Month,
+    Time(DateTime<Utc>),
+}
+
+impl VwapAnchor {
+    fn period_key(&self, time: DateTime<Utc>) -> (i32, u32) {
+        match self {
+            VwapAnchor::Session => (time.year(), time.ordinal()),
+            VwapAnchor::Week => (time.iso_week().year(), time.iso_week().week()),
+            VwapAnchor::Month => (time.year(), time.month()),
+            VwapAnchor::Time(_) => (0, 0),
+        }
+    }
+}
+
+/// VWAP with bands at each multiple of the volume-weighted standard deviation of the typical price.
+pub struct Vwap {
+    pub vwap: Vec<IndicatorData>,
+    pub upper: Vec<Vec<IndicatorData>>,
+    pub lower: Vec<Vec<IndicatorData>>,
+}
+
+/// Volume-weighted average of the typical price, reset whenever the anchor period changes.
+/// A `Time` anchor starts at the first bar at or after that time and never resets.
+pub fn calculate_vwap(data: &[CustomQuote], anchor: VwapAnchor, band_multipliers: &[f64]) -> Vwap {
+    let mut vwap = Vwap {
+        vwap: Vec::new(),
+        upper: vec![Vec::new(); band_multipliers.len()],
+        lower: vec![Vec::new(); band_multipliers.len()],
+    };
+    let (mut volume, mut price_volume, mut squared_volume) = (0.0, 0.0, 0.0);
+    let mut current_period = None;
+
+    for quote in data {
+        if let VwapAnchor::Time(start) = anchor {
+            if quote.time < start {
+                continue;
+            }
+        }
+        let period = anchor.period_key(quote.time);
+        if current_period != Some(period) {
+            current_period = Some(period);
+            (volume, price_volume, squared_volume) = (0.0, 0.0, 0.0);
+        }
+
+        let price = typical_price(quote);
+        volume += volume_of(quote);
+        price_volume += price * volume_of(quote);
+        squared_volume += price * price * volume_of(quote);
+
+        let (average, deviation) = if volume == 0.0 {
+            (price, 0.0)
+        } else {
+            let average = price_volume / volume;
+            (average, (squared_volume / volume - average * average).max(0.0).sqrt())
+        };
+        vwap.vwap.push(IndicatorData { time: quote.time, value: average });
+        for (band, multiplier) in band_multipliers.iter().enumerate() {
+            vwap.upper[band].push(IndicatorData { time: quote.time, value: average + multiplier * deviation });
+            vwap.lower[band].push(IndicatorData { time: quote.time, value: average - multiplier * deviation });
+        }
+    }
+
+    vwap
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct VolumeBin {
+    pub low: f64,
+    pub high: f64,
+    pub volume: f64,
+}
+
+/// Price-by-volume histogram with its point of control and value area.
+#[derive(Serialize, Debug, Clone, Default, PartialEq)]
+pub struct VolumeProfile {
+    pub bins: Vec<VolumeBin>,
+    pub point_of_control: f64,
+    pub value_area_low: f64,
+    pub value_area_high: f64,
+    pub total_volume: f64,
+}
+
+/// Spreads each bar's volume evenly over its high-low range across `bin_count` price bins.
+/// The value area grows from the point of control towards the heavier neighbouring bin until
+/// it holds `value_area_percent` of the volume.
+pub fn calculate_volume_profile(data: &[CustomQuote], bin_count: usize, value_area_percent: f64) -> VolumeProfile {
+    let low = data.iter().map(low_of).fold(f64::MAX, f64::min);
+    let high = data.iter().map(high_of).fold(f64::MIN, f64::max);
+    if data.is_empty() || bin_count == 0 {
+        return VolumeProfile::default();
+    }
+    let bin_size = if high > low { (high - low) / bin_count as f64 } else { 1.0 };
+    let mut bins: Vec<VolumeBin> = (0..bin_count)
+        .map(|i| VolumeBin {
+            low: low + bin_size * i as f64,
+            high: low + bin_size * (i + 1) as f64,
+            volume: 0.0,
+        })
+        .collect();
+
+    let bin_index = |price: f64| (((price - low) / bin_size) as usize).min(bin_count - 1);
+    for quote in data {
+        let (bar_low, bar_high) = (low_of(quote), high_of(quote));
+        let (first, last) = (bin_index(bar_low), bin_index(bar_high));
+        if bar_high <= bar_low {
+            bins[first].volume += volume_of(quote);
+            continue;
+        }
+        for bin in &mut bins[first..=last] {
+            let overlap = bar_high.min(bin.high) - bar_low.max(bin.low);
+            bin.volume += volume_of(quote) * overlap.max(0.0) / (bar_high - bar_low);
+        }
+    }
+
+    let total_volume: f64 = bins.iter().map(|bin| bin.volume).sum();
+    let poc = bins
+        .iter()
+        .enumerate()
+        .fold(0, |best, (i, bin)| if bin.volume > bins[best].volume { i } else { best });
+    let (mut first, mut last) = (poc, poc);
+    let mut area_volume = bins[poc].volume;
+    while area_volume < total_volume * value_area_percent / 100.0 && (first > 0 || last + 1 < bin_count) {
+        let below = first.checked_sub(1).map_or(-1.0, |i| bins[i].volume);
+        let above = bins.get(last + 1).map_or(-1.0, |bin| bin.volume);
+        if above >= below {
+            last += 1;
+            area_volume += above;
+        } else {
+            first -= 1;
+            area_volume += below;
+        }
+    }
+
+    VolumeProfile {
+        point_of_control: (bins[poc].low + bins[poc].high) / 2.0,
+        value_area_low: bins[first].low,
+        value_area_high: bins[last].high,
+        total_volume,
+        bins,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone, Utc};
+
+    fn quotes(bars: &[(f64, f64, f64)]) -> Vec<CustomQuote> {
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        bars.iter()
+            .enumerate()
+            .map(|(i, (high, low, close))| CustomQuote {
+                time: start + Duration::days(i as i64),
+                open: Some(*close),
+                high: Some(*high),
+                low: Some(*low),
+                close: Some(*close),
+                volume: Some(1000),
+            })
+            .collect()
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-9, "{} != {}", actual, expected);
+    }
+
+    #[test]
+    fn test_bollinger_bands() {
+        let data = quotes(&[(3.0, 1.0, 2.0), (5.0, 3.0, 4.0), (7.0, 5.0, 6.0), (5.0, 3.0, 4.0)]);
+        let bands = calculate_bollinger_bands(&data, 2, 2.0);
+        assert_eq!(bands.middle[2].value, 5.0);
+        assert_eq!(bands.upper[2].value, 7.0);
+        assert_eq!(bands.lower[2].value, 3.0);
+
+        let percent_b = calculate_percent_b(&data, &bands);
+        assert_eq!(percent_b[2].value, 0.75);
+        assert_eq!(calculate_bandwidth(&bands)[2].value, 0.8);
+    }
+
+    #[test]
+    fn test_keltner_channels() {
+        let data = quotes(&[(3.0, 1.0, 2.0), (5.0, 3.0, 4.0), (7.0, 5.0, 6.0), (4.0, 2.0, 3.0)]);
+        // EMA(3) of the close: 2, 3, 4.5, 3.75. Wilder ATR(2) of true ranges 2, 3, 3, 4: 2, 2.5, 2.75, 3.375.
+        let channel = calculate_keltner_channels(&data, 3, 2, 2.0);
+        assert_eq!(channel.middle[3].value, 3.75);
+        assert_eq!(channel.upper[1].value, 8.0);
+        assert_eq!(channel.lower[1].value, -2.0);
+        assert_eq!(channel.upper[3].value, 10.5);
+        assert_eq!(channel.lower[3].value, -3.0);
+    }
+
+    #[test]
+    fn test_donchian_channels() {
+        let data = quotes(&[(3.0, 1.0, 2.0), (5.0, 3.0, 4.0), (7.0, 5.0, 6.0), (4.0, 2.0, 3.0)]);
+        let channel = calculate_donchian_channels(&data, 2);
+        assert_eq!(channel.upper[0].value, 3.0);
+        assert_eq!(channel.upper[3].value, 7.0);
+        assert_eq!(channel.lower[3].value, 2.0);
+        assert_eq!(channel.middle[3].value, 4.5);
+    }
+
+    #[test]
+    fn test_range_oscillators() {
+        let data = quotes(&[(3.0, 1.0, 2.0), (5.0, 3.0, 4.0), (7.0, 5.0, 6.0), (4.0, 2.0, 3.0)]);
+        let (fast_k, fast_d) = calculate_stochastic(&data, 2, 1, 2);
+        assert_eq!(fast_k[2].value, 75.0);
+        assert_eq!(fast_k[3].value, 20.0);
+        assert_eq!(fast_d[3].value, 47.5);
+
+        let (slow_k, _) = calculate_stochastic(&data, 2, 2, 2);
+        assert_eq!(slow_k[3].value, 47.5);
+
+        let williams = calculate_williams_r(&data, 2);
+        assert_eq!(williams[3].value, fast_k[3].value - 100.0);
+
+        let roc = calculate_roc(&data, 2);
+        assert_eq!(roc.len(), 2);
+        assert_eq!(roc[0].time, data[2].time);
+        assert_eq!(roc[0].value, 200.0);
+    }
+
+    #[test]
+    fn test_typical_price_oscillators() {
+        // Typical prices 2, 4, 6, 3; true ranges 2, 3, 3, 4; buying pressure 1, 2, 2, 1.
+        let mut data = quotes(&[(3.0, 1.0, 2.0), (5.0, 3.0, 4.0), (7.0, 5.0, 6.0), (4.0, 2.0, 3.0)]);
+
+        // Window 2, 4, 6: mean 4, mean deviation 4/3.
+        let cci = calculate_cci(&data, 3);
+        assert_close(cci[2].value, 2.0 / (0.015 * 4.0 / 3.0));
+        // Window 4, 6, 3: mean 13/3, mean deviation 10/9.
+        assert_close(cci[3].value, -80.0);
+
+        // Raw flows 12000 and 6000 rise, 3000 falls.
+        data[1].volume = Some(3000);
+        let mfi = calculate_mfi(&data, 3);
+        assert_eq!(mfi.len(), 1);
+        assert_close(mfi[0].value, 100.0 - 100.0 / (1.0 + 18000.0 / 3000.0));
+        assert_eq!(calculate_mfi(&data, 2)[0].value, 100.0);
+
+        let ultimate = calculate_ultimate_oscillator(&data, 1, 2, 3);
+        assert_close(ultimate[3].value, 100.0 * (4.0 * 1.0 / 4.0 + 2.0 * 3.0 / 7.0 + 5.0 / 10.0) / 7.0);
+    }
+
+    #[test]
+    fn test_trend_indicators() {
+        let rising = quotes(&[(2.0, 1.0, 1.5), (3.0, 2.0, 2.5), (4.0, 3.0, 3.5), (5.0, 4.0, 4.5)]);
+        let dmi = calculate_dmi(&rising, 2);
+        assert_eq!(dmi.minus_di[3].value, 0.0);
+        assert!(dmi.plus_di[3].value > 0.0);
+        assert!(dmi.adx[3].value > 50.0);
+
+        let sar = calculate_parabolic_sar(&rising, 0.02, 0.2);
+        assert_eq!(sar[0].value, 1.0);
+        assert!(sar.iter().zip(&rising).all(|(sar, quote)| sar.value <= quote.low.unwrap()));
+
+        let supertrend = calculate_supertrend(&rising, 2, 1.0);
+        assert!(supertrend.iter().zip(&rising).all(|(line, quote)| line.value < quote.close.unwrap()));
+
+        let (up, down, oscillator) = calculate_aroon(&rising, 2);
+        assert_eq!(up[3].value, 100.0);
+        assert_eq!(down[3].value, 0.0);
+        assert_eq!(oscillator[3].value, 100.0);
+    }
+
+    #[test]
+    fn test_dmi_values() {
+        // +DM 0, 2, 1, 0 and -DM 0, 0, 0, 3 over true ranges 2, 3, 3, 5; Wilder's smoothing
+        // with period 2 gives TR 2, 2.5, 2.75, 3.875, +DM 0, 1, 1, 0.5 and -DM 0, 0, 0, 1.5.
+        let data = quotes(&[(10.0, 8.0, 9.0), (12.0, 9.0, 11.0), (13.0, 10.0, 12.0), (12.0, 7.0, 8.0)]);
+        let dmi = calculate_dmi(&data, 2);
+        assert_close(dmi.plus_di[1].value, 40.0);
+        assert_close(dmi.plus_di[2].value, 100.0 / 2.75);
+        assert_close(dmi.plus_di[3].value, 50.0 / 3.875);
+        assert_close(dmi.minus_di[3].value, 150.0 / 3.875);
+        // DX 0, 100, 100, 50.
+        assert_close(dmi.adx[1].value, 50.0);
+        assert_close(dmi.adx[2].value, 75.0);
+        assert_close(dmi.adx[3].value, 62.5);
+    }
+
+    #[test]
+    fn test_parabolic_sar_values() {
+        let data = quotes(&[
+            (10.0, 8.0, 9.0),
+            (12.0, 10.0, 11.0),
+            (14.0, 12.0, 13.0),
+            (15.0, 13.0, 14.0),
+            (14.2, 12.5, 13.0),
+            (13.0, 9.0, 10.0),
+            (11.0, 8.0, 9.0),
+        ]);
+        let sar: Vec<f64> = calculate_parabolic_sar(&data, 0.1, 0.2).iter().map(|point| point.value).collect();
+        // The first two steps are held at the low of bar 0.
+        assert_eq!(sar[..3], [8.0, 8.0, 8.0]);
+        // The factor has reached its 0.2 cap: 8 + 0.2 * (14 - 8).
+        assert_close(sar[3], 9.2);
+        assert_close(sar[4], 9.2 + 0.2 * (15.0 - 9.2));
+        // Bar 5's low breaks the SAR: it flips to the extreme high and the factor resets.
+        assert_eq!(sar[5], 15.0);
+        assert_close(sar[6], 15.0 + 0.1 * (9.0 - 15.0));
+    }
+
+    #[test]
+    fn test_supertrend_values() {
+        // ATR(2) 2, 2.5, 2.25, 4.125, 4.0625, 4.53125 around midpoints 9, 11, 12, 8, 7, 9.
+        let data = quotes(&[
+            (10.0, 8.0, 9.0),
+            (12.0, 10.0, 11.0),
+            (13.0, 11.0, 12.0),
+            (10.0, 6.0, 7.0),
+            (9.0, 5.0, 6.0),
+            (11.0, 7.0, 8.0),
+        ]);
+        let line: Vec<f64> = calculate_supertrend(&data, 2, 1.0).iter().map(|point| point.value).collect();
+        assert_eq!(line[..3], [7.0, 8.5, 9.75]);
+        // The lower band holds at 9.75 instead of dropping to 3.875, so the close of 7 flips
+        // the trend down onto the upper band.
+        assert_eq!(line[3], 12.125);
+        assert_eq!(line[4], 11.0625);
+        // The upper band holds rather than widening to 13.53125.
+        assert_eq!(line[5], 11.0625);
+    }
+
+    #[test]
+    fn test_vortex() {
+        let data = quotes(&[(3.0, 1.0, 2.0), (5.0, 3.0, 4.0), (7.0, 5.0, 6.0), (4.0, 2.0, 3.0)]);
+        let (plus, minus) = calculate_vortex(&data, 2);
+        assert_eq!(plus.len(), 2);
+        assert_eq!(plus[0].time, data[2].time);
+        // +VM 4 + 4, -VM 0 + 0 over true ranges 3 + 3.
+        assert_close(plus[0].value, 8.0 / 6.0);
+        assert_eq!(minus[0].value, 0.0);
+        // +VM 4 + 1, -VM 0 + 5 over true ranges 3 + 4.
+        assert_close(plus[1].value, 5.0 / 7.0);
+        assert_close(minus[1].value, 5.0 / 7.0);
+    }
+
+    #[test]
+    fn test_shift_series() {
+        let data = quotes(&[(3.0, 1.0, 2.0), (5.0, 3.0, 4.0), (7.0, 5.0, 6.0)]);
+        let ichimoku = calculate_ichimoku(&data, 1, 2, 3);
+        assert_eq!(ichimoku.senkou_a[2].value, 5.5);
+        assert_eq!(ichimoku.senkou_b[2].value, 4.0);
+
+        let forward = shift_series(&ichimoku.senkou_b, &data, 2, Timeframe::OneDay);
+        assert_eq!(forward.len(), 3);
+        assert_eq!(forward[0].time, data[2].time);
+        // 2024-01-03 is a Wednesday, so two bars later is Friday.
+        assert_eq!(forward[2].time, data[2].time + Duration::days(2));
+
+        let back = shift_series(&ichimoku.chikou, &data, -2, Timeframe::OneDay);
+        assert_eq!(back.len(), 1);
+        assert_eq!(back[0].time, data[0].time);
+        assert_eq!(back[0].value, 6.0);
+    }
+
+    #[test]
+    fn test_volume_indicators() {
+        let mut data = quotes(&[(3.0, 1.0, 3.0), (5.0, 3.0, 4.0), (7.0, 5.0, 5.0), (6.0, 4.0, 6.0)]);
+        data[1].volume = Some(3000);
+
+        let obv = calculate_obv(&data);
+        assert_eq!(obv[1].value, 3000.0);
+        assert_eq!(obv[3].value, 5000.0);
+
+        let accumulation = calculate_accumulation_distribution(&data);
+        assert_eq!(accumulation[0].value, 1000.0);
+        assert_eq!(accumulation[2].value, 0.0);
+
+        // Money flow volumes 1000, 0, -1000, 1000 against volumes 1000, 3000, 1000, 1000.
+        let money_flow = calculate_chaikin_money_flow(&data, 2);
+        assert_eq!(money_flow[0].value, 1.0);
+        assert_eq!(money_flow[1].value, 0.25);
+        assert_eq!(money_flow[2].value, -0.25);
+        assert_eq!(money_flow[3].value, 0.0);
+
+        // EMA(3) of the A/D line is 1000, 1000, 500, 750 and EMA(7) is 1000, 1000, 750, 812.5.
+        let oscillator = calculate_chaikin_oscillator(&data, 3, 7);
+        assert_eq!(oscillator[1].value, 0.0);
+        assert_eq!(oscillator[2].value, -250.0);
+        assert_eq!(oscillator[3].value, -62.5);
+
+        let session = calculate_vwap(&data, VwapAnchor::Session, &[1.0]);
+        assert_eq!(session.vwap[1].value, typical_price(&data[1]));
+        assert_eq!(session.upper[0][1].value, session.vwap[1].value);
+
+        let anchored = calculate_vwap(&data, VwapAnchor::Time(data[2].time), &[1.0]);
+        assert_eq!(anchored.vwap.len(), 2);
+        assert_eq!(anchored.vwap[1].value, (typical_price(&data[2]) + typical_price(&data[3])) / 2.0);
+        assert!(anchored.upper[0][1].value > anchored.vwap[1].value);
+    }
+
+    #[test]
+    fn test_volume_profile() {
+        let mut data = quotes(&[(2.0, 0.0, 1.0), (4.0, 2.0, 3.0), (4.0, 2.0, 3.0), (6.0, 4.0, 5.0)]);
+        data[3].volume = Some(500);
+        let profile = calculate_volume_profile(&data, 3, 70.0);
+        assert_eq!(profile.total_volume, 3500.0);
+        assert_eq!(profile.bins[1].volume, 2000.0);
+        assert_eq!(profile.point_of_control, 3.0);
+        assert_eq!(profile.value_area_low, 0.0);
+        assert_eq!(profile.value_area_high, 4.0);
+    }
+}
@@ -1,41 +1,46 @@
-use chrono::{Duration, Utc};
-use reqwest::StatusCode;
-use crate::{types::{CustomQuote, StockQuote}, STOCK_DATA};
+use chrono::{DateTime, Utc};
+use crate::{providers::provider, types::{CustomQuote, Timeframe}, STOCK_DATA};
 
 use super::fetch_stock_utils::{filter_complete_quotes, transform_to_custom_quotes};
 
-pub  async fn fetch_initial_data(symbol: &str, timeframe: &str) -> Result<Vec<CustomQuote>, String> {
-    let client = reqwest::Client::new();
+pub  async fn fetch_initial_data(symbol: &str, timeframe: Timeframe) -> Result<Vec<CustomQuote>, String> {
     let now = Utc::now();
-    let from = match timeframe {
-        "1M" => now - Duration::days(3),
-        "1H" => now - Duration::weeks(15),
-        "1D" => now - Duration::days(4 * 365),
-        "1WK" => now - Duration::days(7 * 365),
-        _ => return Err("Invalid timeframe".to_string()),
-    };
-    let url = format!(
-        "http://localhost:3000/stock/chart/{}?timeframe={}&period1={}&period2={}",
-        symbol, timeframe, from, now
-    );
-    let response = client.get(&url).send().await.map_err(|e| e.to_string())?;
-    match response.status() {
-        StatusCode::OK => {
-            let stock_data: Vec<StockQuote> = response.json().await.map_err(|e| e.to_string())?;
-            let filtered_data = filter_complete_quotes(stock_data);
-            let custom_quotes = transform_to_custom_quotes(filtered_data);
-            Ok(custom_quotes)
-        }
-        status => Err(format!("Reached end of data {}", status)),
-    }
+    let from = now - timeframe.initial_lookback();
+    let stock_data = provider()
+        .fetch_bars(symbol, timeframe, from, now)
+        .await
+        .map_err(|e| format!("Reached end of data {}", e))?;
+    let filtered_data = filter_complete_quotes(stock_data);
+    let custom_quotes = transform_to_custom_quotes(filtered_data);
+    Ok(custom_quotes)
+}
+
+pub async fn fetch_data_since(symbol: &str, timeframe: Timeframe, since: DateTime<Utc>) -> Result<Vec<CustomQuote>, String> {
+    let stock_data = provider().fetch_bars(symbol, timeframe, since, Utc::now()).await?;
+    let filtered_data = filter_complete_quotes(stock_data);
+    Ok(transform_to_custom_quotes(filtered_data))
 }
 
 pub async fn initialize_data() -> Result<(), String> {
     let symbols = vec!["AAPL", "GOOGL", "AMZN"];
-    let timeframes = vec!["1M", "1H", "1D", "1WK"];
-
     for symbol in symbols {
-        for timeframe in timeframes.iter() {
+        for timeframe in Timeframe::DEFAULTS {
+            let cached_until = {
+                let data_map = STOCK_DATA.read().unwrap();
+                data_map
+                    .get("stocks")
+                    .and_then(|stock_model| stock_model.latest_time(symbol, timeframe))
+            };
+
+            if let Some(latest) = cached_until {
+                let data = fetch_data_since(symbol, timeframe, latest).await?;
+
+                let mut data_map = STOCK_DATA.write().unwrap();
+                let stock_model = data_map.get_mut("stocks").unwrap();
+                stock_model.merge_data(symbol, timeframe, data);
+                continue;
+            }
+
             let data = fetch_initial_data(symbol, timeframe).await?;
 
             let mut data_map = STOCK_DATA.write().unwrap();
@@ -46,17 +51,10 @@ pub async fn initialize_data() -> Result<(), String> {
             let stock_data = stock_model
                 .stock_datas
                 .get(symbol)
-                .and_then(|tf_map| tf_map.get(*timeframe))
+                .and_then(|tf_map| tf_map.get(&timeframe))
                 .unwrap();
             let from = stock_data.from;
-            let new_from = from
-                - match *timeframe {
-                    "1M" => Duration::days(3),
-                    "1H" => Duration::weeks(15),
-                    "1D" => Duration::days(4 * 365),
-                    "1WK" => Duration::days(7 * 365),
-                    _ => return Err("Invalid timeframe".to_string()),
-                };
+            let new_from = from - timeframe.initial_lookback();
 
             print!(
                 "{}, {}, Old from: {}, new from: {}",
@@ -78,7 +76,7 @@ mod tests {
     #[tokio::test]
     async fn test_fetch_initial_data() {
         let symbol = "AAPL";
-        let timeframe = "1M";
+        let timeframe = Timeframe::OneMinute;
         let result = fetch_initial_data(symbol, timeframe).await;
         assert!(result.is_ok());
         let data = result.unwrap();
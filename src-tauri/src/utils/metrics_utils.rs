@@ -0,0 +1,171 @@
+use chrono::{DateTime, Utc};
+
+use crate::types::{PerformanceMetrics, Timeframe, Trade};
+
+/// Per-bar simple returns of an equity curve; bars following a non-positive equity are 0.
+pub fn bar_returns(equity: &[f64]) -> Vec<f64> {
+    equity
+        .windows(2)
+        .map(|pair| if pair[0] > 0.0 { pair[1] / pair[0] - 1.0 } else { 0.0 })
+        .collect()
+}
+
+pub fn mean(values: &[f64]) -> f64 {
+    if values.is_empty() {
+        0.0
+    } else {
+        values.iter().sum::<f64>() / values.len() as f64
+    }
+}
+
+/// Sample standard deviation.
+pub fn std_dev(values: &[f64]) -> f64 {
+    if values.len() < 2 {
+        return 0.0;
+    }
+    let mean = mean(values);
+    let variance = values.iter().map(|value| (value - mean).powi(2)).sum::<f64>() / (values.len() - 1) as f64;
+    variance.sqrt()
+}
+
+/// Sample covariance of two equally long series.
+pub fn covariance(a: &[f64], b: &[f64]) -> f64 {
+    let n = a.len().min(b.len());
+    if n < 2 {
+        return 0.0;
+    }
+    let (mean_a, mean_b) = (mean(&a[..n]), mean(&b[..n]));
+    a.iter().zip(b).map(|(x, y)| (x - mean_a) * (y - mean_b)).sum::<f64>() / (n - 1) as f64
+}
+
+/// Pearson correlation; 0 when either series is constant.
+pub fn correlation(a: &[f64], b: &[f64]) -> f64 {
+    let n = a.len().min(b.len());
+    ratio(covariance(a, b), std_dev(&a[..n]) * std_dev(&b[..n]))
+}
+
+/// Root mean square of the negative returns, the denominator of the Sortino ratio.
+fn downside_deviation(returns: &[f64]) -> f64 {
+    if returns.is_empty() {
+        return 0.0;
+    }
+    let sum: f64 = returns.iter().map(|value| value.min(0.0).powi(2)).sum();
+    (sum / returns.len() as f64).sqrt()
+}
+
+fn ratio(numerator: f64, denominator: f64) -> f64 {
+    if denominator > 0.0 {
+        numerator / denominator
+    } else {
+        0.0
+    }
+}
+
+/// Longest runs of consecutive winning and losing trades (by net P&L).
+fn streaks(trades: &[Trade]) -> (usize, usize) {
+    let (mut wins, mut losses) = (0, 0);
+    let (mut longest_wins, mut longest_losses) = (0, 0);
+    for trade in trades {
+        if trade.net_pnl > 0.0 {
+            wins += 1;
+            losses = 0;
+        } else {
+            losses += 1;
+            wins = 0;
+        }
+        longest_wins = longest_wins.max(wins);
+        longest_losses = longest_losses.max(losses);
+    }
+    (longest_wins, longest_losses)
+}
+
+/// Computes risk-adjusted statistics from the bar-by-bar equity and position path.
+/// Ratios assume a zero risk-free rate and are annualized with `timeframe.periods_per_year()`;
+/// CAGR uses the calendar span between `start` and `end`.
+pub fn calculate_metrics(
+    equity: &[f64],
+    positions: &[f64],
+    trades: &[Trade],
+    max_drawdown_percentage: f64,
+    timeframe: Timeframe,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> PerformanceMetrics {
+    let periods = timeframe.periods_per_year();
+    let returns = bar_returns(equity);
+    let mean_return = mean(&returns);
+
+    let annualized_return = mean_return * periods * 100.0;
+    let volatility = std_dev(&returns) * periods.sqrt() * 100.0;
+    let sharpe_ratio = ratio(mean_return * periods.sqrt(), std_dev(&returns));
+    let sortino_ratio = ratio(mean_return * periods.sqrt(), downside_deviation(&returns));
+
+    let years = (end - start).num_seconds() as f64 / (365.25 * 86400.0);
+    let cagr = match (equity.first(), equity.last()) {
+        (Some(&first), Some(&last)) if years > 0.0 && first > 0.0 && last > 0.0 => {
+            ((last / first).powf(1.0 / years) - 1.0) * 100.0
+        }
+        _ => 0.0,
+    };
+    let calmar_ratio = ratio(cagr, max_drawdown_percentage.abs());
+
+    let wins: Vec<f64> = trades.iter().map(|trade| trade.net_pnl).filter(|pnl| *pnl > 0.0).collect();
+    let losses: Vec<f64> = trades.iter().map(|trade| trade.net_pnl).filter(|pnl| *pnl <= 0.0).collect();
+    let (longest_win_streak, longest_loss_streak) = streaks(trades);
+
+    let exposed = positions.iter().filter(|position| **position != 0.0).count();
+    let exposure_percentage = ratio(exposed as f64, positions.len() as f64) * 100.0;
+
+    PerformanceMetrics {
+        annualized_return,
+        cagr,
+        volatility,
+        sharpe_ratio,
+        sortino_ratio,
+        calmar_ratio,
+        expectancy: mean(&trades.iter().map(|trade| trade.net_pnl).collect::<Vec<f64>>()),
+        average_win: mean(&wins),
+        average_loss: mean(&losses),
+        longest_win_streak,
+        longest_loss_streak,
+        exposure_percentage,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::test_support::trade;
+    use chrono::{Duration, TimeZone};
+
+    #[test]
+    fn test_cagr_and_trade_statistics() {
+        let start = Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap();
+        let end = start + Duration::seconds((2.0 * 365.25 * 86400.0) as i64);
+        let equity = [100.0, 110.0, 99.0, 121.0];
+        let trades = [trade(10.0), trade(20.0), trade(-5.0), trade(-1.0), trade(-3.0), trade(4.0)];
+
+        let metrics = calculate_metrics(&equity, &[0.0, 1.0, 1.0, 0.0], &trades, -10.0, Timeframe::OneDay, start, end);
+        assert!((metrics.cagr - 10.0).abs() < 1e-6);
+        assert!((metrics.calmar_ratio - 1.0).abs() < 1e-6);
+        assert!((metrics.expectancy - 25.0 / 6.0).abs() < 1e-9);
+        assert!((metrics.average_win - 34.0 / 3.0).abs() < 1e-9);
+        assert!((metrics.average_loss + 3.0).abs() < 1e-9);
+        assert_eq!(metrics.longest_win_streak, 2);
+        assert_eq!(metrics.longest_loss_streak, 3);
+        assert_eq!(metrics.exposure_percentage, 50.0);
+        assert!(metrics.sharpe_ratio > 0.0 && metrics.sortino_ratio > 0.0);
+    }
+
+    #[test]
+    fn test_annualization_scales_with_timeframe() {
+        let start = Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap();
+        let equity = [100.0, 101.0, 100.5, 102.0, 101.0];
+        let daily = calculate_metrics(&equity, &[], &[], 0.0, Timeframe::OneDay, start, start);
+        let weekly = calculate_metrics(&equity, &[], &[], 0.0, Timeframe::OneWeek, start, start);
+        let factor = (252.0_f64 / 52.0).sqrt();
+        assert!((daily.volatility / weekly.volatility - factor).abs() < 1e-9);
+        assert!((daily.sharpe_ratio / weekly.sharpe_ratio - factor).abs() < 1e-9);
+        assert_eq!(daily.cagr, 0.0);
+    }
+}
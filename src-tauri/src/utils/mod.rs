@@ -1,4 +1,15 @@
 pub mod indicator_utils;
 pub mod init_data_utils;
 pub mod fetch_stock_utils;
-pub mod backtest_utils;
\ No newline at end of file
+pub mod backtest_utils;
+pub mod benchmark_utils;
+pub mod metrics_utils;
+pub mod monte_carlo_utils;
+pub mod portfolio_utils;
+pub mod sizing_utils;
+pub mod costs_utils;
+pub mod exit_utils;
+pub mod order_utils;
+pub mod import_utils;
+pub mod resample_utils;
+pub mod adjust_utils;
\ No newline at end of file
@@ -0,0 +1,295 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::types::StrategyResult;
+use crate::utils::backtest_utils::drawdown_series;
+use crate::utils::metrics_utils::{bar_returns, mean, std_dev};
+
+pub const MAX_SIMULATIONS: usize = 100_000;
+const HISTOGRAM_BINS: usize = 20;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MonteCarloMethod {
+    /// Replays the trades' returns in random order: same final capital, different paths.
+    #[default]
+    TradeShuffle,
+    /// Draws as many trades as the backtest had, with replacement.
+    TradeResample,
+    /// Rebuilds the equity curve from randomly drawn blocks of consecutive bar returns.
+    BarBootstrap,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct MonteCarloSpec {
+    pub method: MonteCarloMethod,
+    pub simulations: usize,
+    /// Bars per block for `BarBootstrap`, which keeps short-range autocorrelation.
+    pub block_size: usize,
+    /// A path is ruined once equity falls below this percentage of the initial capital.
+    pub ruin_threshold_percent: f64,
+    pub seed: Option<u64>,
+}
+
+impl Default for MonteCarloSpec {
+    fn default() -> Self {
+        Self {
+            method: MonteCarloMethod::default(),
+            simulations: 1000,
+            block_size: 10,
+            ruin_threshold_percent: 50.0,
+            seed: None,
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct HistogramBin {
+    pub from: f64,
+    pub to: f64,
+    pub count: usize,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct Distribution {
+    pub mean: f64,
+    pub std_dev: f64,
+    pub min: f64,
+    pub max: f64,
+    pub p5: f64,
+    pub p25: f64,
+    pub p50: f64,
+    pub p75: f64,
+    pub p95: f64,
+    pub histogram: Vec<HistogramBin>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct MonteCarloResult {
+    pub simulations: usize,
+    pub final_capital: Distribution,
+    pub total_return_percentage: Distribution,
+    pub max_drawdown_percentage: Distribution,
+    pub probability_of_ruin: f64,
+}
+
+/// Linear interpolation between the closest ranks of `sorted`.
+pub fn percentile(sorted: &[f64], percent: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = percent / 100.0 * (sorted.len() - 1) as f64;
+    let (lower, upper) = (rank.floor() as usize, rank.ceil() as usize);
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - lower as f64)
+}
+
+fn distribution(mut values: Vec<f64>) -> Distribution {
+    values.sort_by(f64::total_cmp);
+    let min = values.first().copied().unwrap_or(0.0);
+    let max = values.last().copied().unwrap_or(0.0);
+    let width = (max - min) / HISTOGRAM_BINS as f64;
+
+    let mut histogram: Vec<HistogramBin> = (0..HISTOGRAM_BINS)
+        .map(|bin| HistogramBin {
+            from: min + bin as f64 * width,
+            to: min + (bin + 1) as f64 * width,
+            count: 0,
+        })
+        .collect();
+    for value in &values {
+        let bin = if width > 0.0 { ((value - min) / width) as usize } else { 0 };
+        histogram[bin.min(HISTOGRAM_BINS - 1)].count += 1;
+    }
+
+    Distribution {
+        mean: mean(&values),
+        std_dev: std_dev(&values),
+        min,
+        max,
+        p5: percentile(&values, 5.0),
+        p25: percentile(&values, 25.0),
+        p50: percentile(&values, 50.0),
+        p75: percentile(&values, 75.0),
+        p95: percentile(&values, 95.0),
+        histogram,
+    }
+}
+
+/// Each trade's net P&L as a fraction of the equity it was opened with, replaying the
+/// ledger sequentially from the initial capital.
+pub fn trade_returns(result: &StrategyResult, initial_capital: f64) -> Vec<f64> {
+    let mut equity = initial_capital;
+    result
+        .trades
+        .iter()
+        .map(|trade| {
+            let trade_return = if equity > 0.0 { trade.net_pnl / equity } else { 0.0 };
+            equity += trade.net_pnl;
+            trade_return
+        })
+        .collect()
+}
+
+fn simulate_path(returns: &[f64], spec: &MonteCarloSpec, rng: &mut StdRng) -> Vec<f64> {
+    let n = returns.len();
+    match spec.method {
+        MonteCarloMethod::TradeShuffle => {
+            let mut shuffled = returns.to_vec();
+            for i in (1..n).rev() {
+                shuffled.swap(i, rng.gen_range(0..=i));
+            }
+            shuffled
+        }
+        MonteCarloMethod::TradeResample => (0..n).map(|_| returns[rng.gen_range(0..n)]).collect(),
+        MonteCarloMethod::BarBootstrap => {
+            let block = spec.block_size.clamp(1, n);
+            let mut path = Vec::with_capacity(n);
+            while path.len() < n {
+                let start = rng.gen_range(0..=n - block);
+                path.extend_from_slice(&returns[start..start + block]);
+            }
+            path.truncate(n);
+            path
+        }
+    }
+}
+
+/// Resamples the trades (or bar returns) of a backtest `spec.simulations` times and
+/// summarizes the resulting final capital, return and max drawdown.
+pub fn run_monte_carlo(result: &StrategyResult, spec: &MonteCarloSpec) -> Result<MonteCarloResult, String> {
+    if spec.simulations == 0 || spec.simulations > MAX_SIMULATIONS {
+        return Err(format!("Simulations must be between 1 and {}", MAX_SIMULATIONS));
+    }
+    let initial_capital = *result.equity_curve.first().ok_or("Backtest has no equity curve")?;
+    let returns = match spec.method {
+        MonteCarloMethod::BarBootstrap => bar_returns(&result.equity_curve),
+        _ => trade_returns(result, initial_capital),
+    };
+    if returns.is_empty() {
+        return Err("Backtest has no trades to simulate".to_string());
+    }
+
+    let base_seed = spec.seed.unwrap_or_else(rand::random);
+    let ruin_level = initial_capital * spec.ruin_threshold_percent / 100.0;
+    let paths: Vec<(f64, f64, bool)> = (0..spec.simulations)
+        .into_par_iter()
+        .map(|simulation| {
+            let mut rng = StdRng::seed_from_u64(base_seed.wrapping_add(simulation as u64));
+            let mut equity = vec![initial_capital];
+            for step in simulate_path(&returns, spec, &mut rng) {
+                let last = *equity.last().unwrap();
+                equity.push(last * (1.0 + step));
+            }
+            let max_drawdown = drawdown_series(&equity).into_iter().fold(0.0, f64::min);
+            let ruined = equity.iter().any(|value| *value < ruin_level);
+            (*equity.last().unwrap(), max_drawdown, ruined)
+        })
+        .collect();
+
+    let final_capital: Vec<f64> = paths.iter().map(|path| path.0).collect();
+    let total_return = final_capital
+        .iter()
+        .map(|capital| (capital / initial_capital - 1.0) * 100.0)
+        .collect();
+    let ruined = paths.iter().filter(|path| path.2).count();
+
+    Ok(MonteCarloResult {
+        simulations: spec.simulations,
+        final_capital: distribution(final_capital),
+        total_return_percentage: distribution(total_return),
+        max_drawdown_percentage: distribution(paths.iter().map(|path| path.1).collect()),
+        probability_of_ruin: ruined as f64 / spec.simulations as f64,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::test_support::trade;
+    use crate::types::{BenchmarkComparison, PerformanceMetrics};
+
+    fn result_with_trades(pnls: &[f64]) -> StrategyResult {
+        let mut equity = vec![1000.0];
+        let trades = pnls
+            .iter()
+            .map(|&net_pnl| {
+                equity.push(equity.last().unwrap() + net_pnl);
+                trade(net_pnl)
+            })
+            .collect();
+        StrategyResult {
+            signals: Vec::new(),
+            dates: Vec::new(),
+            num_trades: pnls.len(),
+            winning_trades: 0,
+            losing_trades: 0,
+            winning_percentage: 0.0,
+            profit_factor: 0.0,
+            final_capital: *equity.last().unwrap(),
+            total_return_percentage: 0.0,
+            drawdown: drawdown_series(&equity),
+            equity_curve: equity,
+            max_drawdown_percentage: 0.0,
+            max_drawdown_peak: None,
+            max_drawdown_trough: None,
+            max_drawdown_recovery: None,
+            max_bars_under_water: 0,
+            total_commission: 0.0,
+            total_slippage: 0.0,
+            total_costs: 0.0,
+            trades,
+            metrics: PerformanceMetrics::default(),
+            buy_and_hold: BenchmarkComparison::default(),
+            benchmark: None,
+        }
+    }
+
+    #[test]
+    fn test_trade_shuffle_keeps_final_capital() {
+        let result = result_with_trades(&[100.0, -50.0, 200.0, -300.0, 80.0]);
+        let spec = MonteCarloSpec {
+            simulations: 200,
+            seed: Some(1),
+            ..Default::default()
+        };
+        let simulation = run_monte_carlo(&result, &spec).unwrap();
+        assert!((simulation.final_capital.min - result.final_capital).abs() < 1e-6);
+        assert!((simulation.final_capital.max - result.final_capital).abs() < 1e-6);
+        assert!(simulation.max_drawdown_percentage.min <= simulation.max_drawdown_percentage.max);
+        assert_eq!(simulation.final_capital.histogram.iter().map(|bin| bin.count).sum::<usize>(), 200);
+        assert_eq!(run_monte_carlo(&result, &spec).unwrap(), simulation);
+    }
+
+    #[test]
+    fn test_resampling_and_ruin() {
+        let result = result_with_trades(&[300.0, -600.0, 200.0]);
+        let spec = MonteCarloSpec {
+            method: MonteCarloMethod::TradeResample,
+            simulations: 500,
+            seed: Some(3),
+            ..Default::default()
+        };
+        let simulation = run_monte_carlo(&result, &spec).unwrap();
+        assert!(simulation.final_capital.min < simulation.final_capital.max);
+        assert!(simulation.probability_of_ruin > 0.0 && simulation.probability_of_ruin < 1.0);
+
+        let bootstrap = MonteCarloSpec {
+            method: MonteCarloMethod::BarBootstrap,
+            block_size: 2,
+            ..spec
+        };
+        assert_eq!(run_monte_carlo(&result, &bootstrap).unwrap().simulations, 500);
+        assert!(run_monte_carlo(&result_with_trades(&[]), &MonteCarloSpec::default()).is_err());
+    }
+
+    #[test]
+    fn test_percentile() {
+        let sorted = [1.0, 2.0, 3.0, 4.0, 5.0];
+        assert_eq!(percentile(&sorted, 50.0), 3.0);
+        assert_eq!(percentile(&sorted, 25.0), 2.0);
+        assert!((percentile(&sorted, 90.0) - 4.6).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,139 @@
+use serde::{Deserialize, Serialize};
+
+use crate::types::CustomQuote;
+
+/// How a signal becomes a fill. Offsets are percentages of the signal bar's close; limit
+/// orders wait for a better price, stop orders for a breakout in the signal's direction.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum OrderType {
+    /// Fill at the close of the bar that produced the signal (uses information not yet
+    /// available when the order would have had to be placed).
+    MarketOnClose,
+    #[default]
+    MarketOnNextOpen,
+    Limit { offset_percent: f64 },
+    Stop { offset_percent: f64 },
+    /// A stop that, once touched, becomes a limit `limit_offset_percent` beyond the stop.
+    StopLimit { stop_offset_percent: f64, limit_offset_percent: f64 },
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct OrderConfig {
+    pub order_type: OrderType,
+    /// Bars after the signal bar an unfilled order stays working; `None` is good-till-cancelled.
+    pub expiry_bars: Option<usize>,
+}
+
+/// A working order placed on `placed_index`, filled on a later bar.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PendingOrder {
+    pub is_buy: bool,
+    pub placed_index: usize,
+    limit: Option<f64>,
+    stop: Option<f64>,
+    triggered: bool,
+}
+
+impl PendingOrder {
+    /// `None` for `MarketOnClose`, which fills immediately instead of working.
+    pub fn place(order_type: OrderType, is_buy: bool, reference_price: f64, index: usize) -> Option<Self> {
+        let direction = if is_buy { 1.0 } else { -1.0 };
+        let offset = |percent: f64| reference_price * (1.0 + direction * percent / 100.0);
+        let (limit, stop) = match order_type {
+            OrderType::MarketOnClose => return None,
+            OrderType::MarketOnNextOpen => (None, None),
+            OrderType::Limit { offset_percent } => (Some(offset(-offset_percent)), None),
+            OrderType::Stop { offset_percent } => (None, Some(offset(offset_percent))),
+            OrderType::StopLimit { stop_offset_percent, limit_offset_percent } => {
+                let stop = offset(stop_offset_percent);
+                (Some(stop * (1.0 + direction * limit_offset_percent / 100.0)), Some(stop))
+            }
+        };
+        Some(Self {
+            is_buy,
+            placed_index: index,
+            limit,
+            stop,
+            triggered: false,
+        })
+    }
+
+    pub fn is_expired(&self, index: usize, expiry_bars: Option<usize>) -> bool {
+        expiry_bars.is_some_and(|bars| index - self.placed_index > bars)
+    }
+
+    /// Tries to fill against `quote` and returns the fill price. Prices gapping through a
+    /// level fill at the open.
+    pub fn try_fill(&mut self, quote: &CustomQuote) -> Option<f64> {
+        let close = quote.close?;
+        let open = quote.open.unwrap_or(close);
+        let high = quote.high.unwrap_or(close);
+        let low = quote.low.unwrap_or(close);
+        // Orders compare prices as if buying; sells are mirrored.
+        let better = |a: f64, b: f64| if self.is_buy { a <= b } else { a >= b };
+        let best = |a: f64, b: f64| if better(a, b) { a } else { b };
+        let favorable_extreme = if self.is_buy { low } else { high };
+        let adverse_extreme = if self.is_buy { high } else { low };
+
+        if let (Some(stop), false) = (self.stop, self.triggered) {
+            if better(adverse_extreme, stop) && adverse_extreme != stop {
+                return None;
+            }
+            self.triggered = true;
+            let trigger_price = if better(open, stop) { stop } else { open };
+            return match self.limit {
+                None => Some(trigger_price),
+                Some(limit) if better(trigger_price, limit) => Some(trigger_price),
+                Some(limit) => better(favorable_extreme, limit).then_some(limit),
+            };
+        }
+
+        match self.limit {
+            None => Some(open),
+            Some(limit) => better(favorable_extreme, limit).then_some(best(open, limit)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::test_support::quote;
+
+    #[test]
+    fn test_market_and_limit_orders() {
+        assert_eq!(PendingOrder::place(OrderType::MarketOnClose, true, 100.0, 0), None);
+        let mut market = PendingOrder::place(OrderType::MarketOnNextOpen, true, 100.0, 0).unwrap();
+        assert_eq!(market.try_fill(&quote(101.0, 102.0, 99.0, 100.0)), Some(101.0));
+
+        let mut buy_limit = PendingOrder::place(OrderType::Limit { offset_percent: 2.0 }, true, 100.0, 0).unwrap();
+        assert_eq!(buy_limit.try_fill(&quote(100.0, 101.0, 99.0, 100.0)), None);
+        assert_eq!(buy_limit.try_fill(&quote(99.0, 99.5, 97.0, 98.0)), Some(98.0));
+        assert_eq!(buy_limit.try_fill(&quote(96.0, 99.0, 95.0, 98.0)), Some(96.0));
+
+        let mut sell_limit = PendingOrder::place(OrderType::Limit { offset_percent: 1.0 }, false, 100.0, 0).unwrap();
+        assert_eq!(sell_limit.try_fill(&quote(100.0, 101.5, 99.0, 100.0)), Some(101.0));
+
+        assert!(!buy_limit.is_expired(3, Some(3)));
+        assert!(buy_limit.is_expired(4, Some(3)));
+        assert!(!buy_limit.is_expired(400, None));
+    }
+
+    #[test]
+    fn test_stop_and_stop_limit_orders() {
+        let mut buy_stop = PendingOrder::place(OrderType::Stop { offset_percent: 1.0 }, true, 100.0, 0).unwrap();
+        assert_eq!(buy_stop.try_fill(&quote(100.0, 100.5, 99.0, 100.0)), None);
+        assert_eq!(buy_stop.try_fill(&quote(100.0, 102.0, 99.0, 101.5)), Some(101.0));
+
+        let mut sell_stop = PendingOrder::place(OrderType::Stop { offset_percent: 1.0 }, false, 100.0, 0).unwrap();
+        assert_eq!(sell_stop.try_fill(&quote(97.0, 98.0, 96.0, 97.0)), Some(97.0));
+
+        let stop_limit = OrderType::StopLimit { stop_offset_percent: 1.0, limit_offset_percent: 1.0 };
+        let mut gapped = PendingOrder::place(stop_limit, true, 100.0, 0).unwrap();
+        // Opens above the 102.01 limit and never trades back down to it.
+        assert_eq!(gapped.try_fill(&quote(104.0, 105.0, 103.0, 104.0)), None);
+        assert_eq!(gapped.try_fill(&quote(103.0, 103.5, 101.0, 102.0)), Some(101.0 * 1.01));
+    }
+}
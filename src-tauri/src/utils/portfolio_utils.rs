@@ -0,0 +1,391 @@
+use std::collections::{BTreeSet, HashMap};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::types::CustomQuote;
+use crate::utils::backtest_utils::{drawdown_series, drawdown_stats, BacktestConfig};
+use crate::utils::costs_utils::fill_order;
+use crate::utils::metrics_utils::{bar_returns, correlation, std_dev};
+
+const INITIAL_CAPITAL: f64 = 100000.0;
+
+/// How much of the portfolio's equity a position targets.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Allocation {
+    /// `1 / max_positions` of equity per position.
+    #[default]
+    EqualWeight,
+    /// Percent of equity per symbol; symbols missing from the map are not traded.
+    FixedWeights { weights: HashMap<String, f64> },
+    /// Weights proportional to the inverse volatility of each symbol's last `lookback`
+    /// bar returns, normalized over the basket and capped at `1 / max_positions`.
+    InverseVolatility { lookback: usize },
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct PortfolioSpec {
+    pub max_positions: usize,
+    pub allocation: Allocation,
+    /// Resize open positions back to their target weight every this many bars.
+    pub rebalance_bars: Option<usize>,
+}
+
+impl Default for PortfolioSpec {
+    fn default() -> Self {
+        Self {
+            max_positions: 5,
+            allocation: Allocation::default(),
+            rebalance_bars: None,
+        }
+    }
+}
+
+/// One basket member: its bars and the strategy's signals for them.
+pub struct SymbolSeries {
+    pub symbol: String,
+    pub quotes: Vec<CustomQuote>,
+    pub signals: Vec<String>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct SymbolContribution {
+    pub symbol: String,
+    pub num_trades: usize,
+    /// Realized P&L net of costs, in account currency.
+    pub net_pnl: f64,
+    /// `net_pnl` as a percentage of the initial capital.
+    pub contribution_percentage: f64,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct PortfolioResult {
+    pub dates: Vec<String>,
+    pub equity_curve: Vec<f64>,
+    pub drawdown: Vec<f64>,
+    pub final_capital: f64,
+    pub total_return_percentage: f64,
+    pub max_drawdown_percentage: f64,
+    pub num_trades: usize,
+    pub total_costs: f64,
+    pub contributions: Vec<SymbolContribution>,
+    /// Pearson correlation of the symbols' bar returns, in `contributions` order. Each pair
+    /// only uses the bars on which both symbols have a close.
+    pub correlation: Vec<Vec<f64>>,
+}
+
+#[derive(Default)]
+struct Holding {
+    quantity: f64,
+    cost_basis: f64,
+    pending: Option<bool>,
+    last_close: Option<f64>,
+    num_trades: usize,
+    net_pnl: f64,
+}
+
+struct Portfolio<'a> {
+    config: &'a BacktestConfig,
+    cash: f64,
+    total_costs: f64,
+}
+
+impl Portfolio<'_> {
+    fn buy(&mut self, holding: &mut Holding, price: f64, quantity: f64, quote: &CustomQuote) {
+        let fill = fill_order(&self.config.commission, &self.config.slippage, true, price, quantity, quote);
+        if holding.quantity == 0.0 {
+            holding.num_trades += 1;
+        }
+        self.cash -= fill.price * quantity + fill.commission;
+        self.total_costs += fill.commission + fill.slippage;
+        holding.cost_basis += fill.price * quantity + fill.commission;
+        holding.quantity += quantity;
+    }
+
+    fn sell(&mut self, holding: &mut Holding, price: f64, quantity: f64, quote: &CustomQuote) {
+        let quantity = quantity.min(holding.quantity);
+        let fill = fill_order(&self.config.commission, &self.config.slippage, false, price, quantity, quote);
+        let basis = holding.cost_basis * quantity / holding.quantity;
+        self.cash += fill.price * quantity - fill.commission;
+        self.total_costs += fill.commission + fill.slippage;
+        holding.net_pnl += fill.price * quantity - fill.commission - basis;
+        holding.cost_basis -= basis;
+        holding.quantity -= quantity;
+    }
+}
+
+fn market_value(holdings: &[Holding]) -> f64 {
+    holdings
+        .iter()
+        .map(|holding| holding.quantity * holding.last_close.unwrap_or(0.0))
+        .sum()
+}
+
+fn target_weights(series: &[SymbolSeries], spec: &PortfolioSpec, indices: &[Option<usize>]) -> Vec<f64> {
+    let max_positions = spec.max_positions.max(1) as f64;
+    match &spec.allocation {
+        Allocation::EqualWeight => vec![1.0 / max_positions; series.len()],
+        Allocation::FixedWeights { weights } => series
+            .iter()
+            .map(|member| weights.get(&member.symbol).copied().unwrap_or(0.0) / 100.0)
+            .collect(),
+        Allocation::InverseVolatility { lookback } => {
+            let inverse: Vec<f64> = series
+                .iter()
+                .zip(indices)
+                .map(|(member, index)| {
+                    let end = index.map_or(0, |index| index + 1);
+                    let closes: Vec<f64> = member.quotes[end.saturating_sub(lookback + 1)..end]
+                        .iter()
+                        .filter_map(|quote| quote.close)
+                        .collect();
+                    let volatility = std_dev(&bar_returns(&closes));
+                    if volatility > 0.0 { 1.0 / volatility } else { 0.0 }
+                })
+                .collect();
+            let total: f64 = inverse.iter().sum();
+            inverse
+                .iter()
+                .map(|value| if total > 0.0 { (value / total).min(1.0 / max_positions) } else { 0.0 })
+                .collect()
+        }
+    }
+}
+
+/// Correlation of two symbols' returns between the timeline bars where both have a close.
+fn pair_correlation(a: &[Option<f64>], b: &[Option<f64>]) -> f64 {
+    let (a, b): (Vec<f64>, Vec<f64>) = a
+        .iter()
+        .zip(b)
+        .filter_map(|pair| match pair {
+            (Some(a), Some(b)) => Some((*a, *b)),
+            _ => None,
+        })
+        .unzip();
+    correlation(&bar_returns(&a), &bar_returns(&b))
+}
+
+/// Long-only backtest of a basket sharing one account. A "buy" signal opens a position
+/// at the symbol's next open if fewer than `max_positions` are held, sized to its target
+/// weight of current equity (limited by available cash); a "sell" closes it at the next
+/// open. Only the commission and slippage models of `config` apply.
+pub fn backtest_portfolio(series: &[SymbolSeries], config: &BacktestConfig, spec: &PortfolioSpec) -> PortfolioResult {
+    let timeline: Vec<DateTime<Utc>> = series
+        .iter()
+        .flat_map(|member| member.quotes.iter().map(|quote| quote.time))
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    let positions: Vec<HashMap<DateTime<Utc>, usize>> = series
+        .iter()
+        .map(|member| member.quotes.iter().enumerate().map(|(i, quote)| (quote.time, i)).collect())
+        .collect();
+
+    let mut portfolio = Portfolio {
+        config,
+        cash: INITIAL_CAPITAL,
+        total_costs: 0.0,
+    };
+    let mut holdings: Vec<Holding> = series.iter().map(|_| Holding::default()).collect();
+    let mut last_index: Vec<Option<usize>> = vec![None; series.len()];
+    let mut closes: Vec<Vec<Option<f64>>> = vec![Vec::with_capacity(timeline.len()); series.len()];
+    let mut equity_curve = Vec::with_capacity(timeline.len());
+
+    for (step, time) in timeline.iter().enumerate() {
+        let today: Vec<Option<usize>> = positions.iter().map(|map| map.get(time).copied()).collect();
+        let equity = equity_curve.last().copied().unwrap_or(INITIAL_CAPITAL);
+        let weights = target_weights(series, spec, &last_index);
+
+        // Exits first so their proceeds can fund entries on the same open.
+        for is_buy in [false, true] {
+            for (s, member) in series.iter().enumerate() {
+                let Some(index) = today[s] else { continue };
+                if holdings[s].pending != Some(is_buy) {
+                    continue;
+                }
+                holdings[s].pending = None;
+                let quote = &member.quotes[index];
+                let Some(open) = quote.open.or(quote.close) else { continue };
+                let held = holdings.iter().filter(|holding| holding.quantity > 0.0).count();
+                if !is_buy {
+                    let quantity = holdings[s].quantity;
+                    portfolio.sell(&mut holdings[s], open, quantity, quote);
+                } else if held < spec.max_positions {
+                    let quantity = (equity * weights[s]).min(portfolio.cash).max(0.0) / open;
+                    if quantity.floor() > 0.0 {
+                        portfolio.buy(&mut holdings[s], open, quantity.floor(), quote);
+                    }
+                }
+            }
+        }
+
+        for (s, member) in series.iter().enumerate() {
+            let Some(index) = today[s] else { continue };
+            last_index[s] = Some(index);
+            let holding = &mut holdings[s];
+            holding.last_close = member.quotes[index].close.or(holding.last_close);
+            holding.pending = match (member.signals.get(index).map(String::as_str), holding.quantity > 0.0) {
+                (Some("buy"), false) => Some(true),
+                (Some("sell"), true) => Some(false),
+                _ => holding.pending,
+            };
+        }
+
+        if spec.rebalance_bars.is_some_and(|bars| bars > 0 && step > 0 && step % bars == 0) {
+            let equity = portfolio.cash + market_value(&holdings);
+            let weights = target_weights(series, spec, &last_index);
+            for (s, member) in series.iter().enumerate() {
+                let (Some(index), Some(price)) = (today[s], holdings[s].last_close) else { continue };
+                if holdings[s].quantity == 0.0 {
+                    continue;
+                }
+                let target = (equity * weights[s] / price).floor();
+                let difference = target - holdings[s].quantity;
+                let quote = &member.quotes[index];
+                if difference < 0.0 {
+                    portfolio.sell(&mut holdings[s], price, -difference, quote);
+                } else if difference > 0.0 {
+                    let affordable = (portfolio.cash / price).floor();
+                    if affordable > 0.0 {
+                        portfolio.buy(&mut holdings[s], price, difference.min(affordable), quote);
+                    }
+                }
+            }
+        }
+
+        equity_curve.push(portfolio.cash + market_value(&holdings));
+        for (s, member) in series.iter().enumerate() {
+            closes[s].push(today[s].and_then(|index| member.quotes[index].close));
+        }
+    }
+
+    for (s, member) in series.iter().enumerate() {
+        if let (Some(index), Some(price)) = (last_index[s], holdings[s].last_close) {
+            let quantity = holdings[s].quantity;
+            if quantity > 0.0 {
+                portfolio.sell(&mut holdings[s], price, quantity, &member.quotes[index]);
+            }
+        }
+    }
+    let final_capital = portfolio.cash;
+    if let Some(last) = equity_curve.last_mut() {
+        *last = final_capital;
+    }
+
+    let correlation = closes
+        .iter()
+        .map(|a| closes.iter().map(|b| pair_correlation(a, b)).collect())
+        .collect();
+    let drawdown = drawdown_series(&equity_curve);
+
+    PortfolioResult {
+        dates: timeline.iter().map(|time| time.to_string()).collect(),
+        final_capital,
+        total_return_percentage: (final_capital / INITIAL_CAPITAL - 1.0) * 100.0,
+        max_drawdown_percentage: drawdown_stats(&drawdown).max_drawdown,
+        num_trades: holdings.iter().map(|holding| holding.num_trades).sum(),
+        total_costs: portfolio.total_costs,
+        contributions: series
+            .iter()
+            .zip(&holdings)
+            .map(|(member, holding)| SymbolContribution {
+                symbol: member.symbol.clone(),
+                num_trades: holding.num_trades,
+                net_pnl: holding.net_pnl,
+                contribution_percentage: holding.net_pnl / INITIAL_CAPITAL * 100.0,
+            })
+            .collect(),
+        correlation,
+        equity_curve,
+        drawdown,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+
+    fn member(symbol: &str, closes: &[f64], signals: &[&str]) -> SymbolSeries {
+        let start = Utc.with_ymd_and_hms(2023, 1, 2, 0, 0, 0).unwrap();
+        SymbolSeries {
+            symbol: symbol.to_string(),
+            quotes: closes
+                .iter()
+                .enumerate()
+                .map(|(day, &close)| CustomQuote {
+                    high: Some(close),
+                    volume: Some(1000),
+                    open: Some(close),
+                    low: Some(close),
+                    close: Some(close),
+                    time: start + Duration::days(day as i64),
+                })
+                .collect(),
+            signals: signals.iter().map(|signal| signal.to_string()).collect(),
+        }
+    }
+
+    fn free() -> BacktestConfig {
+        let mut config = BacktestConfig::default();
+        config.commission.schedule = crate::utils::costs_utils::CommissionSchedule::Flat { amount: 0.0 };
+        config
+    }
+
+    #[test]
+    fn test_shared_capital_and_max_positions() {
+        let series = vec![
+            member("AAA", &[10.0, 10.0, 12.0, 12.0], &["buy", "hold", "hold", "hold"]),
+            member("BBB", &[20.0, 20.0, 20.0, 25.0], &["buy", "hold", "sell", "hold"]),
+            member("CCC", &[5.0, 5.0, 5.0, 5.0], &["buy", "hold", "hold", "hold"]),
+        ];
+        let spec = PortfolioSpec {
+            max_positions: 2,
+            ..Default::default()
+        };
+        let result = backtest_portfolio(&series, &free(), &spec);
+
+        assert_eq!(result.contributions[0].num_trades, 1);
+        assert_eq!(result.contributions[1].num_trades, 1);
+        assert_eq!(result.contributions[2].num_trades, 0);
+        assert!((result.contributions[0].net_pnl - 5000.0 * 2.0).abs() < 1e-6);
+        // The sell signal on day 3 fills at the next open.
+        assert!((result.contributions[1].net_pnl - 2500.0 * 5.0).abs() < 1e-6);
+        assert!((result.final_capital - (INITIAL_CAPITAL + 22_500.0)).abs() < 1e-6);
+        assert_eq!(result.equity_curve.len(), 4);
+        assert!((result.correlation[0][0] - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_correlation_ignores_bars_before_listing() {
+        let mut late = member("BBB", &[5.0, 5.5, 6.05, 5.5], &["hold"; 4]);
+        for quote in &mut late.quotes {
+            quote.time += Duration::days(2);
+        }
+        let series = vec![member("AAA", &[10.0, 20.0, 10.0, 11.0, 12.1, 11.0], &["hold"; 6]), late];
+        let result = backtest_portfolio(&series, &free(), &PortfolioSpec::default());
+        assert_eq!(result.dates.len(), 6);
+        assert!((result.correlation[0][1] - 1.0).abs() < 1e-9);
+        assert!((result.correlation[1][0] - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_fixed_weights_and_rebalancing() {
+        let series = vec![member("AAA", &[10.0, 10.0, 20.0, 20.0], &["buy", "hold", "hold", "hold"])];
+        let spec = PortfolioSpec {
+            max_positions: 1,
+            allocation: Allocation::FixedWeights {
+                weights: HashMap::from([("AAA".to_string(), 50.0)]),
+            },
+            rebalance_bars: Some(2),
+        };
+        let result = backtest_portfolio(&series, &free(), &spec);
+        // 5000 shares bought at 10; at 20 equity is 150k, so 1250 are sold to get back to 50%.
+        assert!((result.contributions[0].net_pnl - 50_000.0).abs() < 1e-6);
+        assert!((result.equity_curve[2] - 150_000.0).abs() < 1e-6);
+        assert!((result.final_capital - 150_000.0).abs() < 1e-6);
+        assert_eq!(result.num_trades, 1);
+    }
+}
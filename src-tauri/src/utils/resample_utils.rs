@@ -0,0 +1,184 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
+
+use crate::types::{CustomQuote, Timeframe};
+
+/// Whether bars of `source` can be aggregated into `target` without splitting a source bar.
+/// Intraday targets need a whole multiple of the source interval; weeks don't nest in months.
+pub fn can_resample(source: Timeframe, target: Timeframe) -> bool {
+    if source.bar_duration() >= target.bar_duration() {
+        return false;
+    }
+    match target {
+        Timeframe::OneDay => true,
+        Timeframe::OneWeek => source != Timeframe::OneMonth,
+        Timeframe::OneMonth => source != Timeframe::OneWeek,
+        _ => {
+            let source_minutes = source.bar_duration().num_minutes();
+            target.bar_duration().num_minutes() % source_minutes == 0
+        }
+    }
+}
+
+/// The coarsest timeframe in `available` that can be resampled into `target`.
+pub fn best_source(target: Timeframe, available: &[Timeframe]) -> Option<Timeframe> {
+    available
+        .iter()
+        .copied()
+        .filter(|source| can_resample(*source, target))
+        .max_by_key(|source| source.bar_duration())
+}
+
+/// Aggregates `quotes` (sorted by time) into `target` bars: first open, highest high,
+/// lowest low, last close and summed volume. Each output bar is stamped with the time of
+/// its first source bar, like the bars the provider returns.
+///
+/// Intraday buckets are aligned to the session: the first bar of each UTC day is the
+/// anchor, so 1H bars starting 13:30 aggregate into 4H bars at 13:30 and 17:30.
+/// Daily bars group by UTC date, weekly by ISO week (Monday start), monthly by calendar month.
+pub fn resample(quotes: &[CustomQuote], target: Timeframe) -> Vec<CustomQuote> {
+    let mut session_open: HashMap<NaiveDate, DateTime<Utc>> = HashMap::new();
+    for quote in quotes {
+        session_open
+            .entry(quote.time.date_naive())
+            .and_modify(|open| *open = (*open).min(quote.time))
+            .or_insert(quote.time);
+    }
+
+    let mut resampled: Vec<CustomQuote> = Vec::new();
+    let mut current_bucket: Option<DateTime<Utc>> = None;
+
+    for quote in quotes {
+        let bucket = bucket_start(quote.time, target, &session_open);
+        if current_bucket == Some(bucket) {
+            let bar = resampled.last_mut().unwrap();
+            bar.open = bar.open.or(quote.open);
+            bar.high = max_option(bar.high, quote.high);
+            bar.low = min_option(bar.low, quote.low);
+            bar.close = quote.close.or(bar.close);
+            bar.volume = match (bar.volume, quote.volume) {
+                (Some(total), Some(volume)) => Some(total + volume),
+                (total, volume) => total.or(volume),
+            };
+        } else {
+            current_bucket = Some(bucket);
+            resampled.push(quote.clone());
+        }
+    }
+
+    resampled
+}
+
+fn bucket_start(
+    time: DateTime<Utc>,
+    target: Timeframe,
+    session_open: &HashMap<NaiveDate, DateTime<Utc>>,
+) -> DateTime<Utc> {
+    let date = time.date_naive();
+    let midnight = date.and_hms_opt(0, 0, 0).unwrap().and_utc();
+    match target {
+        Timeframe::OneDay => midnight,
+        Timeframe::OneWeek => midnight - Duration::days(date.weekday().num_days_from_monday() as i64),
+        Timeframe::OneMonth => NaiveDate::from_ymd_opt(date.year(), date.month(), 1)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+            .and_utc(),
+        _ => {
+            let anchor = session_open.get(&date).copied().unwrap_or(midnight);
+            let step = target.bar_duration().num_seconds();
+            let offset = (time - anchor).num_seconds().div_euclid(step) * step;
+            anchor + Duration::seconds(offset)
+        }
+    }
+}
+
+fn max_option(a: Option<f64>, b: Option<f64>) -> Option<f64> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.max(b)),
+        (a, b) => a.or(b),
+    }
+}
+
+fn min_option(a: Option<f64>, b: Option<f64>) -> Option<f64> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn bar(time: DateTime<Utc>, open: f64, high: f64, low: f64, close: f64, volume: u64) -> CustomQuote {
+        CustomQuote {
+            high: Some(high),
+            volume: Some(volume),
+            open: Some(open),
+            low: Some(low),
+            close: Some(close),
+            time,
+        }
+    }
+
+    #[test]
+    fn test_hourly_to_four_hours_aligned_to_session() {
+        let open = Utc.with_ymd_and_hms(2024, 3, 4, 14, 30, 0).unwrap();
+        let mut quotes: Vec<CustomQuote> = (0..7)
+            .map(|i| {
+                let price = 100.0 + i as f64;
+                bar(open + Duration::hours(i), price, price + 2.0, price - 1.0, price + 1.0, 10)
+            })
+            .collect();
+        quotes.push(bar(open + Duration::days(1), 200.0, 201.0, 199.0, 200.5, 5));
+
+        let resampled = resample(&quotes, Timeframe::FourHours);
+        assert_eq!(resampled.len(), 3);
+        assert_eq!(resampled[0].time, open);
+        assert_eq!(resampled[0].open, Some(100.0));
+        assert_eq!(resampled[0].high, Some(105.0));
+        assert_eq!(resampled[0].low, Some(99.0));
+        assert_eq!(resampled[0].close, Some(104.0));
+        assert_eq!(resampled[0].volume, Some(40));
+        assert_eq!(resampled[1].time, open + Duration::hours(4));
+        assert_eq!(resampled[1].volume, Some(30));
+        assert_eq!(resampled[2].time, open + Duration::days(1));
+    }
+
+    #[test]
+    fn test_daily_to_weekly_and_monthly() {
+        let monday = Utc.with_ymd_and_hms(2024, 1, 29, 14, 30, 0).unwrap();
+        let quotes: Vec<CustomQuote> = (0..10)
+            .filter(|day| day % 7 < 5)
+            .map(|day| bar(monday + Duration::days(day), 10.0, 11.0, 9.0, 10.0 + day as f64, 1))
+            .collect();
+
+        let weekly = resample(&quotes, Timeframe::OneWeek);
+        assert_eq!(weekly.len(), 2);
+        assert_eq!(weekly[0].volume, Some(5));
+        assert_eq!(weekly[0].close, Some(14.0));
+        assert_eq!(weekly[1].time, monday + Duration::days(7));
+
+        let monthly = resample(&quotes, Timeframe::OneMonth);
+        assert_eq!(monthly.len(), 2);
+        assert_eq!(monthly[0].close, Some(12.0));
+        assert_eq!(monthly[1].time, monday + Duration::days(3));
+    }
+
+    #[test]
+    fn test_can_resample_and_best_source() {
+        assert!(can_resample(Timeframe::OneMinute, Timeframe::FiveMinutes));
+        assert!(can_resample(Timeframe::OneHour, Timeframe::FourHours));
+        assert!(!can_resample(Timeframe::ThirtyMinutes, Timeframe::FifteenMinutes));
+        assert!(!can_resample(Timeframe::OneWeek, Timeframe::OneMonth));
+        assert!(can_resample(Timeframe::OneDay, Timeframe::OneMonth));
+
+        let available = [Timeframe::OneMinute, Timeframe::OneHour, Timeframe::OneDay];
+        assert_eq!(best_source(Timeframe::FourHours, &available), Some(Timeframe::OneHour));
+        assert_eq!(best_source(Timeframe::OneWeek, &available), Some(Timeframe::OneDay));
+        assert_eq!(best_source(Timeframe::OneMinute, &available), None);
+    }
+}
@@ -0,0 +1,108 @@
+use serde::{Deserialize, Serialize};
+
+use crate::types::Trade;
+
+/// How many shares a new position takes. Quantities are rounded down to whole shares;
+/// an entry that sizes to zero is skipped.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PositionSizing {
+    FixedShares { shares: f64 },
+    /// Spend a fixed amount of currency per trade.
+    FixedNotional { amount: f64 },
+    /// Spend a percentage of current equity per trade.
+    PercentEquity { percent: f64 },
+    /// Risk `risk_percent` of equity per `atr_multiple` ATRs of adverse movement.
+    VolatilityTarget { risk_percent: f64, atr_period: usize, atr_multiple: f64 },
+    /// Spend `fraction` of the Kelly percentage estimated from the trades closed so far,
+    /// or `fallback_percent` of equity until there is at least one win and one loss.
+    Kelly { fraction: f64, fallback_percent: f64 },
+}
+
+impl Default for PositionSizing {
+    fn default() -> Self {
+        PositionSizing::FixedShares { shares: 20.0 }
+    }
+}
+
+impl PositionSizing {
+    /// ATR period the model needs precomputed, if any.
+    pub fn atr_period(&self) -> Option<usize> {
+        match self {
+            PositionSizing::VolatilityTarget { atr_period, .. } => Some(*atr_period),
+            _ => None,
+        }
+    }
+
+    pub fn quantity(&self, equity: f64, price: f64, atr: Option<f64>, trades: &[Trade]) -> f64 {
+        if price <= 0.0 || equity <= 0.0 {
+            return 0.0;
+        }
+        let quantity = match self {
+            PositionSizing::FixedShares { shares } => *shares,
+            PositionSizing::FixedNotional { amount } => amount / price,
+            PositionSizing::PercentEquity { percent } => equity * percent / 100.0 / price,
+            PositionSizing::VolatilityTarget { risk_percent, atr_multiple, .. } => {
+                match atr.map(|atr| atr * atr_multiple) {
+                    Some(risk_per_share) if risk_per_share > 0.0 => equity * risk_percent / 100.0 / risk_per_share,
+                    _ => 0.0,
+                }
+            }
+            PositionSizing::Kelly { fraction, fallback_percent } => {
+                let percent = kelly_fraction(trades).map_or(*fallback_percent, |kelly| kelly * fraction * 100.0);
+                equity * percent / 100.0 / price
+            }
+        };
+        quantity.max(0.0).floor()
+    }
+}
+
+/// Kelly percentage `W - (1 - W) / R` from win rate `W` and payoff ratio `R`, clamped to
+/// 0..=1. `None` until both a winning and a losing trade exist.
+pub fn kelly_fraction(trades: &[Trade]) -> Option<f64> {
+    let wins: Vec<f64> = trades.iter().map(|trade| trade.net_pnl).filter(|pnl| *pnl > 0.0).collect();
+    let losses: Vec<f64> = trades.iter().map(|trade| -trade.net_pnl).filter(|loss| *loss >= 0.0).collect();
+    if wins.is_empty() || losses.is_empty() {
+        return None;
+    }
+    let average_loss = losses.iter().sum::<f64>() / losses.len() as f64;
+    if average_loss == 0.0 {
+        return Some(1.0);
+    }
+    let win_rate = wins.len() as f64 / trades.len() as f64;
+    let payoff = (wins.iter().sum::<f64>() / wins.len() as f64) / average_loss;
+    Some((win_rate - (1.0 - win_rate) / payoff).clamp(0.0, 1.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::test_support::trade;
+
+    #[test]
+    fn test_sizing_models_are_price_aware() {
+        let equity = 100_000.0;
+        let notional = PositionSizing::FixedNotional { amount: 9_000.0 };
+        assert_eq!(notional.quantity(equity, 3_000.0, None, &[]), 3.0);
+        assert_eq!(notional.quantity(equity, 30.0, None, &[]), 300.0);
+
+        let percent = PositionSizing::PercentEquity { percent: 10.0 };
+        assert_eq!(percent.quantity(equity, 30.0, None, &[]), 333.0);
+
+        let volatility = PositionSizing::VolatilityTarget { risk_percent: 1.0, atr_period: 14, atr_multiple: 2.0 };
+        assert_eq!(volatility.quantity(equity, 30.0, Some(2.5), &[]), 200.0);
+        assert_eq!(volatility.quantity(equity, 30.0, None, &[]), 0.0);
+        assert_eq!(PositionSizing::default().quantity(equity, 3_000.0, None, &[]), 20.0);
+    }
+
+    #[test]
+    fn test_kelly_fraction() {
+        assert_eq!(kelly_fraction(&[trade(10.0)]), None);
+        let trades = [trade(20.0), trade(20.0), trade(-10.0), trade(-10.0)];
+        assert!((kelly_fraction(&trades).unwrap() - 0.25).abs() < 1e-9);
+
+        let kelly = PositionSizing::Kelly { fraction: 0.5, fallback_percent: 5.0 };
+        assert_eq!(kelly.quantity(100_000.0, 100.0, None, &trades), 125.0);
+        assert_eq!(kelly.quantity(100_000.0, 100.0, None, &[]), 50.0);
+    }
+}
@@ -179,6 +179,28 @@ const Backtest: React.FC = () => {
                 Total Return Percentage:{" "}
                 {result.total_return_percentage.toFixed(2)}%
               </p>
+              <p className={theme.dropdownText}>
+                Buy & Hold Return:{" "}
+                {result.buy_and_hold.total_return_percentage.toFixed(2)}%
+              </p>
+              <p className={theme.dropdownText}>
+                Total Costs: ${result.total_costs.toFixed(2)}
+              </p>
+              <p className={theme.dropdownText}>
+                Max Drawdown: {result.max_drawdown_percentage.toFixed(2)}%
+              </p>
+              <p className={theme.dropdownText}>
+                Longest Time Under Water: {result.max_bars_under_water} bars
+              </p>
+              <p className={theme.dropdownText}>
+                CAGR: {result.metrics.cagr.toFixed(2)}%
+              </p>
+              <p className={theme.dropdownText}>
+                Sharpe Ratio: {result.metrics.sharpe_ratio.toFixed(2)}
+              </p>
+              <p className={theme.dropdownText}>
+                Sortino Ratio: {result.metrics.sortino_ratio.toFixed(2)}
+              </p>
             </div>
           </div>
         </div>
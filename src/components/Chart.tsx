@@ -14,17 +14,17 @@ import {
 import { FC, useEffect, useRef } from "react";
 import { themeAtom } from "../store/atoms";
 import { useChartData } from "../store/chartdata";
-import { IndicatorData, useIndicatorStore } from "../store/indicators";
+import { useIndicatorStore } from "../store/indicators";
 import { useSidebarLabels } from "../store/labels";
 import { useTimeStamp } from "../store/timestamp";
 import { StockChartData } from "../types";
 import {
+  findIndicatorInfo,
   getBackendData,
   handleIndicatorChange,
-  indicatorColors,
   isBefore2000,
   isTimeRangeExceeding,
-  show_charts
+  showIndicator
 } from "./utils/ChartUtil";
 
 const Chart: FC<{ data: any[] }> = ({ data }) => {
@@ -46,6 +46,14 @@ const Chart: FC<{ data: any[] }> = ({ data }) => {
   const indicatorsToDelete = useIndicatorStore(
     (state) => state.indicatorsToDelete
   );
+  const indicatorInfos = useIndicatorStore((state) => state.indicatorInfos);
+  const loadIndicatorInfos = useIndicatorStore(
+    (state) => state.loadIndicatorInfos
+  );
+
+  useEffect(() => {
+    loadIndicatorInfos();
+  }, [loadIndicatorInfos]);
 
   useEffect(() => {
     if (chartContainerRef.current && !chartRef.current) {
@@ -203,7 +211,12 @@ const Chart: FC<{ data: any[] }> = ({ data }) => {
     activeIndicatorsArray.forEach((type) => {
       addIndicator(label, timeframe, type);
     });
-    handleIndicatorChange(chartRef.current!, activeIndicators, chartRef);
+    handleIndicatorChange(
+      chartRef.current!,
+      activeIndicators,
+      chartRef,
+      indicatorInfos
+    );
   }, [data, label, timeframe]);
 
   useEffect(() => {
@@ -211,104 +224,34 @@ const Chart: FC<{ data: any[] }> = ({ data }) => {
     activeIndicatorsArray.forEach((type) => {
       if (!seriesReferences.has(type)) {
         const data = activeIndicators.get(type);
-        const key = type.split(" ")[0];
-        if ((key === "SMA" && data) || (key === "EMA" && data)) {
-          const lineSeries = chartRef.current!.addLineSeries({
-            color: indicatorColors[key],
-            lineWidth: 1,
-            priceLineVisible: false,
-            priceScaleId: "ma"
-          });
-          const convertedData = data[0]!.map((item) => ({
-            time: (new Date(item.time).getTime() / 1000) as UTCTimestamp,
-            value: item.value
-          }));
-          lineSeries.setData(convertedData as any);
-          seriesReferences.set(type, [lineSeries]);
-        } else if (key === "MACD" && data) {
-          const convertedDatas: IndicatorData[][] = [];
-          for (let i = 0; i < data.length; i++) {
-            const convertedData = data[i]!.map((item) => ({
-              time: (new Date(item.time).getTime() / 1000) as UTCTimestamp,
-              value: item.value
-            }));
-            convertedDatas.push(convertedData);
-          }
-          let mapMacd = show_charts(
-            chartRef.current!,
-            convertedDatas as IndicatorData[][]
+        const info = findIndicatorInfo(indicatorInfos, type);
+        if (data && info) {
+          seriesReferences.set(
+            type,
+            showIndicator(chartRef.current!, type, info, data)
           );
-          seriesReferences.set(type, Array.from(mapMacd.values()));
-        } else if (key === "RSI" && data) {
-          const rsiSeries = chartRef.current!.addLineSeries({
-            color: indicatorColors["RSI"],
-            lineWidth: 1,
-            priceLineVisible: false,
-            priceScaleId: "rsi"
-          });
-
-          const convertedData = data[0]!.map((item) => ({
-            time: (new Date(item.time).getTime() / 1000) as UTCTimestamp,
-            value: item.value
-          }));
-
-          rsiSeries.setData(convertedData as any);
-          seriesReferences.set(type, [rsiSeries]);
-        } else if (key === "VOLUME" && data) {
-          const volumeSeries = chartRef.current!.addHistogramSeries({
-            color: indicatorColors["VOLUME"],
-            priceFormat: {
-              type: "volume"
-            },
-            priceScaleId: "volume"
-          });
-          const convertedData = data[0]!.map((item) => ({
-            time: (new Date(item.time).getTime() / 1000) as UTCTimestamp,
-            value: item.value
-          }));
-          volumeSeries.setData(convertedData as any);
-          seriesReferences.set(type, [volumeSeries]);
         }
       }
     });
-    handleIndicatorChange(chartRef.current!, activeIndicators, chartRef);
-  }, [activeIndicators, seriesReferences]);
+    handleIndicatorChange(
+      chartRef.current!,
+      activeIndicators,
+      chartRef,
+      indicatorInfos
+    );
+  }, [activeIndicators, seriesReferences, indicatorInfos]);
 
   useEffect(() => {
     if (chartRef.current) {
       indicatorsToDelete.forEach((type) => {
         chartRef.current!.removeSeries(type);
       });
-      const activeIndicatorsArray = Array.from(activeIndicators.keys());
-      let hasRsi = false;
-      let hasMacd = false;
-      let hasMa = false;
-      activeIndicatorsArray.forEach((type) => {
-        const key = type.split(" ")[0];
-        if (key === "RSI") {
-          hasRsi = true;
-        } else if (key === "MACD") {
-          hasMacd = true;
-        } else if (key === "SMA" || key === "EMA") {
-          hasMa = true;
-        }
-      });
-      if (!hasRsi && !hasMacd) {
-        chartRef.current!.priceScale("price").applyOptions({
-          scaleMargins: {
-            top: 0,
-            bottom: 0.05
-          }
-        });
-        if (hasMa) {
-          chartRef.current!.priceScale("ma").applyOptions({
-            scaleMargins: {
-              top: 0,
-              bottom: 0.05
-            }
-          });
-        }
-      }
+      handleIndicatorChange(
+        chartRef.current,
+        activeIndicators,
+        chartRef,
+        indicatorInfos
+      );
     }
 
     indicatorsToDelete.length = 0;
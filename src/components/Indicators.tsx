@@ -1,11 +1,15 @@
 import { useAtomValue } from "jotai";
 import { FC, useEffect, useRef, useState } from "react";
 import { themeAtom } from "../store/atoms";
-import { useIndicatorStore } from "../store/indicators";
+import { indicatorKey, useIndicatorStore } from "../store/indicators";
 import { useSidebarLabels } from "../store/labels";
 import { useTheme } from "../store/theme";
 import { useTimeStamp } from "../store/timestamp";
-import { predefinedIndicators } from "./utils/IndicatorUtil";
+import {
+  defaultValues,
+  indicatorVariants,
+  validateValues
+} from "./utils/IndicatorUtil";
 
 const Indicators: FC = () => {
   const currentTheme = useAtomValue(themeAtom);
@@ -24,12 +28,32 @@ const Indicators: FC = () => {
   const addIndicator = useIndicatorStore((state) => state.addIndicator);
   const removeIndicator = useIndicatorStore((state) => state.removeIndicator);
   const activeIndicators = useIndicatorStore((state) => state.activeIndicators);
+  const indicatorInfos = useIndicatorStore((state) => state.indicatorInfos);
+  const loadIndicatorInfos = useIndicatorStore(
+    (state) => state.loadIndicatorInfos
+  );
+  const [customValues, setCustomValues] = useState<number[]>([]);
   const label = useSidebarLabels((state) => state.label);
   const timeframe = useTimeStamp((state) => state.timestamp);
 
+  useEffect(() => {
+    loadIndicatorInfos();
+  }, [loadIndicatorInfos]);
+
+  const selectedInfo = indicatorInfos.find((info) => info.id === selectedType);
+  const customError = selectedInfo
+    ? validateValues(selectedInfo, customValues)
+    : null;
+
+  const selectType = (id: string | null) => {
+    setSelectedType(id);
+    const info = indicatorInfos.find((info) => info.id === id);
+    setCustomValues(info ? defaultValues(info) : []);
+  };
+
   const toggleDropdown = () => {
     setIsOpen(!isOpen);
-    setSelectedType(null);
+    selectType(null);
   };
 
   const toggleIndicatorMenu = () => {
@@ -98,13 +122,14 @@ const Indicators: FC = () => {
                       Select Indicator Type
                     </h2>
                     <ul>
-                      {predefinedIndicators.map((indicator) => (
+                      {indicatorInfos.map((info) => (
                         <li
-                          key={indicator.type}
+                          key={info.id}
                           className={`cursor-pointer py-2 px-4 ${theme.variantHoverBg} rounded ${theme.dropdownText} `}
-                          onClick={() => setSelectedType(indicator.type)}
+                          onClick={() => selectType(info.id)}
+                          title={info.name}
                         >
-                          {indicator.type}
+                          {info.id}
                         </li>
                       ))}
                     </ul>
@@ -117,9 +142,8 @@ const Indicators: FC = () => {
                       {selectedType} Variants
                     </h2>
                     <ul>
-                      {predefinedIndicators
-                        .find((indicator) => indicator.type === selectedType)
-                        ?.variants.map((variant) => (
+                      {selectedInfo &&
+                        indicatorVariants(selectedInfo).map((variant) => (
                           <li
                             key={variant}
                             className={`cursor-pointer py-2 px-4 ${theme.variantHoverBg} rounded ${theme.dropdownText}`}
@@ -131,9 +155,52 @@ const Indicators: FC = () => {
                           </li>
                         ))}
                     </ul>
+                    {selectedInfo && selectedInfo.params.length > 0 && (
+                      <div className="mt-4">
+                        {selectedInfo.params.map((param, i) => (
+                          <label
+                            key={param.name}
+                            className={`flex justify-between items-center py-1 ${theme.dropdownText}`}
+                          >
+                            <span>{param.label}</span>
+                            <input
+                              type="number"
+                              className={`w-20 px-2 py-1 rounded border ${theme.borderColor} ${theme.buttonBg} ${theme.textColor}`}
+                              min={param.min}
+                              max={param.max}
+                              step={param.kind === "Integer" ? 1 : "any"}
+                              value={customValues[i] ?? param.default}
+                              onChange={(event) => {
+                                const values = [...customValues];
+                                values[i] = parseFloat(event.target.value);
+                                setCustomValues(values);
+                              }}
+                            />
+                          </label>
+                        ))}
+                        {customError && (
+                          <p className="text-red-500 text-sm py-1">
+                            {customError}
+                          </p>
+                        )}
+                        <button
+                          className={`mt-2 px-4 py-2 w-full rounded transition duration-150 ease-in-out ${theme.buttonBg} ${theme.buttonHoverBg} ${theme.textColor} border ${theme.borderColor}`}
+                          disabled={customError !== null}
+                          onClick={() =>
+                            addIndicator(
+                              label,
+                              timeframe,
+                              indicatorKey(selectedInfo.id, customValues)
+                            )
+                          }
+                        >
+                          Add {indicatorKey(selectedInfo.id, customValues)}
+                        </button>
+                      </div>
+                    )}
                     <button
                       className={`mt-4 px-4 py-2 rounded transition duration-150 ease-in-out ${theme.buttonBg} ${theme.buttonHoverBg} ${theme.textColor} border ${theme.borderColor}`}
-                      onClick={() => setSelectedType(null)}
+                      onClick={() => selectType(null)}
                     >
                       Back
                     </button>
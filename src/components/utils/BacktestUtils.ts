@@ -1,5 +1,54 @@
 export const strategies = ["MACD", "Triple EMA", "Alligator"];
 
+export interface Trade {
+  side: "long" | "short";
+  entry_time: string;
+  entry_price: number;
+  exit_time: string;
+  exit_price: number;
+  exit_reason:
+    | "signal"
+    | "stop_loss"
+    | "trailing_stop"
+    | "take_profit"
+    | "max_bars"
+    | "end_of_data";
+  quantity: number;
+  commission: number;
+  slippage: number;
+  gross_pnl: number;
+  net_pnl: number;
+  bars_held: number;
+  mae: number;
+  mfe: number;
+}
+
+export interface PerformanceMetrics {
+  annualized_return: number;
+  cagr: number;
+  volatility: number;
+  sharpe_ratio: number;
+  sortino_ratio: number;
+  calmar_ratio: number;
+  expectancy: number;
+  average_win: number;
+  average_loss: number;
+  longest_win_streak: number;
+  longest_loss_streak: number;
+  exposure_percentage: number;
+}
+
+export interface BenchmarkComparison {
+  symbol: string;
+  total_return_percentage: number;
+  excess_return_percentage: number;
+  alpha: number;
+  beta: number;
+  information_ratio: number;
+  tracking_error: number;
+  relative_curve: number[];
+}
+
 export interface StrategyResult {
   signals: string[];
   dates: string[];
@@ -10,4 +59,18 @@ export interface StrategyResult {
   profit_factor: number;
   final_capital: number;
   total_return_percentage: number;
+  equity_curve: number[];
+  drawdown: number[];
+  max_drawdown_percentage: number;
+  max_drawdown_peak: string | null;
+  max_drawdown_trough: string | null;
+  max_drawdown_recovery: string | null;
+  max_bars_under_water: number;
+  total_commission: number;
+  total_slippage: number;
+  total_costs: number;
+  trades: Trade[];
+  metrics: PerformanceMetrics;
+  buy_and_hold: BenchmarkComparison;
+  benchmark: BenchmarkComparison | null;
 }
@@ -1,15 +1,11 @@
 import { invoke } from "@tauri-apps/api/tauri";
-import { IChartApi, ISeriesApi, SeriesOptionsMap } from "lightweight-charts";
-import { IndicatorData } from "../../store/indicators";
-
-export const indicatorColors: { [key: string]: string } = {
-  SMA: "#ff6464",
-  EMA: "#6464ff",
-  RSI: "#64ff64",
-  MACD: "#855085",
-  signalLine: "#b9b964",
-  macdHistogram: "#64ffff"
-};
+import {
+  IChartApi,
+  ISeriesApi,
+  SeriesOptionsMap,
+  UTCTimestamp
+} from "lightweight-charts";
+import { IndicatorData, IndicatorInfo } from "../../store/indicators";
 
 export type BackendData = {
   from: string;
@@ -36,191 +32,102 @@ export function isTimeRangeExceeding(
   return fromVisible - fromBackend <= timeframeMillis;
 }
 
+export const findIndicatorInfo = (
+  infos: IndicatorInfo[],
+  type: string
+): IndicatorInfo | undefined => {
+  const id = type.split(" ")[0];
+  return infos.find((info) => info.id === id);
+};
+
+const priceScaleFor = (info: IndicatorInfo, type: string) => {
+  if (info.pane === "overlay") {
+    return "price";
+  }
+  if (info.pane === "volume") {
+    return "volume";
+  }
+  return type;
+};
+
+// Lays out the price pane, the volume strip and one stacked pane per separate indicator.
 export function handleIndicatorChange(
   _chart: IChartApi,
   activeIndicators: Map<string, IndicatorData[][]>,
-  chartRef: React.RefObject<IChartApi>
+  chartRef: React.RefObject<IChartApi>,
+  infos: IndicatorInfo[]
 ) {
-  const rsiScaleMargins = {
-    top: 0.7,
-    bottom: 0.05
-  };
-  const macdScaleMargins = {
-    top: 0.7,
-    bottom: 0.05
-  };
-  const rsiScaleMarginsWithMacd = {
-    top: 0.7,
-    bottom: 0.15
-  };
-  const macdScaleMarginsWithRsi = {
-    top: 0.85,
-    bottom: 0
-  };
-  const maScaleMarginsNothing = {
-    top: 0.05,
-    bottom: 0.05
-  };
-  const maScaleMarginsHist = {
-    top: 0.05,
-    bottom: 0.3
-  };
-  const volumeMarginsNothing = {
-    top: 0.7,
-    bottom: 0
-  };
-  const volumeMarginsHist = {
-    top: 0.6,
-    bottom: 0.3
-  };
-  if (chartRef.current) {
-    const activeIndicatorsArray = Array.from(activeIndicators.keys());
-    let hasRsi = false;
-    let hasMacd = false;
-    let hasMa = false;
-    let hasVolume = false;
-    activeIndicatorsArray.forEach((type) => {
-      const key = type.split(" ")[0];
-      if (key === "RSI") {
-        hasRsi = true;
-      } else if (key === "MACD") {
-        hasMacd = true;
-      } else if (key === "SMA" || key === "EMA") {
-        hasMa = true;
-      } else if (key === "VOLUME") {
-        hasVolume = true;
-      }
-    });
-
-    if (hasRsi || hasMacd) {
-      chartRef.current
-        .priceScale("price")
-        .applyOptions({ scaleMargins: maScaleMarginsHist });
-    } else {
-      chartRef.current
-        .priceScale("price")
-        .applyOptions({ scaleMargins: maScaleMarginsNothing });
-    }
-    if (hasMa) {
-      if (hasRsi || hasMacd) {
-        chartRef.current
-          .priceScale("ma")
-          .applyOptions({ scaleMargins: maScaleMarginsHist });
-      } else if (!hasRsi && !hasMacd) {
-        chartRef.current
-          .priceScale("ma")
-          .applyOptions({ scaleMargins: maScaleMarginsNothing });
-      }
-    }
-    if (hasVolume) {
-      if (hasRsi || hasMacd) {
-        chartRef.current.priceScale("volume").applyOptions({
-          scaleMargins: volumeMarginsHist
-        });
-      }
-      if (!hasRsi && !hasMacd) {
-        chartRef.current.priceScale("volume").applyOptions({
-          scaleMargins: volumeMarginsNothing
-        });
-      }
-    }
-    if (hasRsi) {
-      if (hasMacd) {
-        chartRef.current.priceScale("rsi").applyOptions({
-          scaleMargins: rsiScaleMarginsWithMacd
-        });
-      } else {
-        chartRef.current.priceScale("rsi").applyOptions({
-          scaleMargins: rsiScaleMargins
-        });
-      }
-    }
-    if (hasMacd) {
-      if (hasRsi) {
-        chartRef.current.priceScale("macdLine").applyOptions({
-          scaleMargins: macdScaleMarginsWithRsi
-        });
-        chartRef.current.priceScale("macdHistogram").applyOptions({
-          scaleMargins: macdScaleMarginsWithRsi
-        });
-        chartRef.current.priceScale("signalLine").applyOptions({
-          scaleMargins: macdScaleMarginsWithRsi
-        });
-      } else {
-        chartRef.current.priceScale("macdLine").applyOptions({
-          scaleMargins: macdScaleMargins
-        });
-        chartRef.current.priceScale("macdHistogram").applyOptions({
-          scaleMargins: macdScaleMargins
-        });
-        chartRef.current.priceScale("signalLine").applyOptions({
-          scaleMargins: macdScaleMargins
-        });
-      }
-    }
+  if (!chartRef.current) {
+    return;
   }
-}
-
-export const show_charts = (chart: IChartApi, datas: IndicatorData[][]) => {
-  let histMap = show_histogram(chart, datas[2], "macdHistogram");
-  let macd = datas[0];
-  let signal = datas[1];
-  let maMap = show_mas(
-    chart,
-    [macd, signal],
-    [indicatorColors["MACD"], indicatorColors["signalLine"]],
-    ["macdLine", "signalLine"]
+  const activeIndicatorsArray = Array.from(activeIndicators.keys());
+  const separate = activeIndicatorsArray.filter(
+    (type) => findIndicatorInfo(infos, type)?.pane === "separate"
+  );
+  const hasVolume = activeIndicatorsArray.some(
+    (type) => findIndicatorInfo(infos, type)?.pane === "volume"
   );
+  const hasPanes = separate.length > 0;
 
-  let merged: Map<String, ISeriesApi<keyof SeriesOptionsMap>> = new Map([
-    ...histMap,
-    ...maMap
-  ]);
-  return merged;
-};
+  chartRef.current.priceScale("price").applyOptions({
+    scaleMargins: hasPanes
+      ? { top: 0.05, bottom: 0.3 }
+      : { top: 0.05, bottom: 0.05 }
+  });
+  if (hasVolume) {
+    chartRef.current.priceScale("volume").applyOptions({
+      scaleMargins: hasPanes ? { top: 0.6, bottom: 0.3 } : { top: 0.7, bottom: 0 }
+    });
+  }
 
-const show_mas = (
-  chart: IChartApi,
-  datasma: any[],
-  colors: string[],
-  priceScaleIds: string[]
-) => {
-  let lineSeries;
-  let returnMap = new Map<String, ISeriesApi<keyof SeriesOptionsMap>>();
-  for (let i = 0; i < datasma.length; i++) {
-    lineSeries = chart.addLineSeries({
-      priceScaleId: priceScaleIds[i],
-      color: colors[i],
-      lineWidth: 1,
-      priceLineVisible: false,
-      priceFormat: {
-        type: "price",
-        precision: 6
+  const paneTop = 0.72;
+  const paneHeight = (1 - paneTop) / Math.max(separate.length, 1);
+  separate.forEach((type, i) => {
+    const gap = i < separate.length - 1 ? 0.02 : 0;
+    chartRef.current!.priceScale(type).applyOptions({
+      scaleMargins: {
+        top: paneTop + paneHeight * i,
+        bottom: Math.max(0, 1 - paneTop - paneHeight * (i + 1)) + gap
       }
     });
-    lineSeries.setData(datasma[i]);
-    returnMap.set(priceScaleIds[i], lineSeries);
-  }
-  return returnMap;
-};
+  });
+}
 
-const show_histogram = (
+// Adds one series per indicator output, styled from the backend's output specs.
+export const showIndicator = (
   chart: IChartApi,
-  histogram: any[],
-  priceScaleId: string
+  type: string,
+  info: IndicatorInfo,
+  datas: IndicatorData[][]
 ) => {
-  let histogramMap = new Map<String, ISeriesApi<keyof SeriesOptionsMap>>();
-  let histogramSeries = chart.addHistogramSeries({
-    priceScaleId: priceScaleId,
-    color: indicatorColors[priceScaleId],
-    priceLineVisible: false,
-    priceFormat: {
-      type: "volume",
-      precision: 6
-    }
+  const priceScaleId = priceScaleFor(info, type);
+  return info.outputs.map((output, i) => {
+    const series: ISeriesApi<keyof SeriesOptionsMap> =
+      output.style === "histogram"
+        ? chart.addHistogramSeries({
+            priceScaleId,
+            color: output.color,
+            priceLineVisible: false,
+            priceFormat:
+              info.pane === "volume"
+                ? { type: "volume" }
+                : { type: "price", precision: 4, minMove: 0.0001 }
+          })
+        : chart.addLineSeries({
+            priceScaleId,
+            color: output.color,
+            lineWidth: 1,
+            lineVisible: output.style === "line",
+            pointMarkersVisible: output.style === "points",
+            pointMarkersRadius: 2,
+            priceLineVisible: false,
+            lastValueVisible: false
+          });
+    const convertedData = (datas[i] ?? []).map((item) => ({
+      time: (new Date(item.time).getTime() / 1000) as UTCTimestamp,
+      value: item.value
+    }));
+    series.setData(convertedData as any);
+    return series;
   });
-
-  histogramSeries.setData(histogram);
-  histogramMap.set(priceScaleId, histogramSeries);
-  return histogramMap;
 };
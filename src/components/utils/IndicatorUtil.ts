@@ -1,34 +1,25 @@
-type PredefinedIndicator = {
-  type: string;
-  variants: string[];
-};
+import { IndicatorInfo, indicatorKey } from "../../store/indicators";
+
+// Menu entries for an indicator, one per preset parameter set offered by the backend.
+export const indicatorVariants = (info: IndicatorInfo): string[] =>
+  info.presets.map((values) => indicatorKey(info.id, values));
 
-export const predefinedIndicators: PredefinedIndicator[] = [
-  {
-    type: "SMA",
-    variants: [
-      "SMA 5",
-      "SMA 8",
-      "SMA 13",
-      "SMA 21",
-      "SMA 50",
-      "SMA 100",
-      "SMA 200"
-    ]
-  },
-  {
-    type: "EMA",
-    variants: [
-      "EMA 5",
-      "EMA 8",
-      "EMA 13",
-      "EMA 21",
-      "EMA 50",
-      "EMA 100",
-      "EMA 200"
-    ]
-  },
-  { type: "RSI", variants: ["RSI 7", "RSI 14", "RSI 21"] },
-  { type: "MACD", variants: ["MACD 12 26 9"] },
-  { type: "VOLUME", variants: ["VOLUME"] }
-];
+export const defaultValues = (info: IndicatorInfo): number[] =>
+  info.params.map((param) => param.default);
+
+export const validateValues = (
+  info: IndicatorInfo,
+  values: number[]
+): string | null => {
+  for (let i = 0; i < info.params.length; i++) {
+    const param = info.params[i];
+    const value = values[i];
+    if (Number.isNaN(value) || value < param.min || value > param.max) {
+      return `${param.label} must be between ${param.min} and ${param.max}`;
+    }
+    if (param.kind === "Integer" && !Number.isInteger(value)) {
+      return `${param.label} must be an integer`;
+    }
+  }
+  return null;
+};
@@ -7,7 +7,33 @@ export interface IndicatorData {
   value: number;
 }
 
+export type ParamSpec = {
+  name: string;
+  label: string;
+  kind: "Integer" | "Float";
+  default: number;
+  min: number;
+  max: number;
+};
+
+export type OutputSpec = {
+  name: string;
+  style: "line" | "histogram" | "points";
+  color: string;
+};
+
+export type IndicatorInfo = {
+  id: string;
+  name: string;
+  pane: "overlay" | "volume" | "separate";
+  params: ParamSpec[];
+  outputs: OutputSpec[];
+  presets: number[][];
+};
+
 export interface IndicatorState {
+  indicatorInfos: IndicatorInfo[];
+  loadIndicatorInfos: () => void;
   activeIndicators: Map<string, IndicatorData[][]>;
   seriesReferences: Map<string, ISeriesApi<keyof SeriesOptionsMap>[]>;
   indicatorsToDelete: ISeriesApi<keyof SeriesOptionsMap>[];
@@ -21,33 +47,37 @@ export interface IndicatorFetchedData {
   value: number;
 }
 
+// Indicator keys are the id followed by its parameter values, e.g. "MACD 12 26 9".
+export const indicatorKey = (id: string, values: number[]) =>
+  [id, ...values].join(" ");
+
+const fetchIndicator = (label: string, timeframe: string, type: string) => {
+  const [variant, ...values] = type.split(" ");
+  return invoke<IndicatorFetchedData[][]>("get_indicators", {
+    symbol: label,
+    timeframe,
+    variant,
+    values: values.map(Number)
+  });
+};
+
 export const useIndicatorStore = create<IndicatorState>((set, get) => ({
+  indicatorInfos: [],
+  loadIndicatorInfos: async () => {
+    if (get().indicatorInfos.length > 0) {
+      return;
+    }
+    const indicatorInfos = await invoke<IndicatorInfo[]>("list_indicators");
+    set({ indicatorInfos });
+  },
   activeIndicators: new Map(),
   seriesReferences: new Map(),
   indicatorsToDelete: [],
   addIndicator: async (label, timeframe, type) => {
-    const variant = type.split(" ")[0];
-    let lengths = [];
     if (get().activeIndicators.has(type)) {
       return;
     }
-
-    if (variant === "MACD") {
-      for (let i = 0; i < 3; i++) {
-        lengths.push(parseInt(type.split(" ")[i + 1]));
-      }
-    } else if (variant !== "VOLUME") {
-      lengths.push(parseInt(type.split(" ")[1]));
-    }
-    const fetchIndicatorData = await invoke<IndicatorFetchedData[][]>(
-      "get_indicators",
-      {
-        symbol: label,
-        timeframe,
-        variant,
-        lengths
-      }
-    );
+    const fetchIndicatorData = await fetchIndicator(label, timeframe, type);
 
     set((state) => {
       const activeIndicators = new Map(state.activeIndicators);
@@ -60,23 +90,10 @@ export const useIndicatorStore = create<IndicatorState>((set, get) => ({
       const state = get();
       const activeIndicators = new Map(state.activeIndicators);
       for (const type of activeIndicators.keys()) {
-        const variant = type.split(" ")[0];
-        let lengths = [];
-        if (variant === "MACD") {
-          for (let i = 0; i < 3; i++) {
-            lengths.push(parseInt(type.split(" ")[i + 1]));
-          }
-        } else if (variant !== "VOLUME") {
-          lengths.push(parseInt(type.split(" ")[1]));
-        }
-        const fetchIndicatorData = await invoke<IndicatorFetchedData[][]>(
-          "get_indicators",
-          {
-            symbol: label,
-            timeframe,
-            variant,
-            lengths
-          }
+        const fetchIndicatorData = await fetchIndicator(
+          label,
+          timeframe,
+          type
         );
         activeIndicators.set(type, fetchIndicatorData);
       }